@@ -0,0 +1,66 @@
+use std::{collections::BTreeMap, env, fmt::Write, fs, io, path::{Path, PathBuf}};
+
+/// Parses the number between `prefix` and `suffix` in a file name such as `y2024` or `d05.rs`, if it has exactly
+/// `digits` digits.
+fn number(name: &str, prefix: &str, digits: usize, suffix: &str) -> Option<u16> {
+    let number = name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    (number.len() == digits && number.bytes().all(|b| b.is_ascii_digit())).then(|| number.parse().ok())?
+}
+
+/// Finds the modules of the days, `src/y20XX/dNN.rs`, sorted by year and day.
+fn discover(src: &Path) -> io::Result<BTreeMap<u16, BTreeMap<u8, PathBuf>>> {
+    let mut years = BTreeMap::new();
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let Some(year) = entry.file_name().to_str().and_then(|n| number(n, "y", 4, "")) else { continue };
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let mut days = BTreeMap::new();
+        for day in fs::read_dir(entry.path())? {
+            let day = day?;
+            if let Some(number) = day.file_name().to_str().and_then(|n| number(n, "d", 2, ".rs")) {
+                days.insert(number as u8, day.path());
+            }
+        }
+        if !days.is_empty() {
+            years.insert(year, days);
+        }
+    }
+    Ok(years)
+}
+
+/// Module declarations of the years and their days, included by `main.rs`.
+fn modules(years: &BTreeMap<u16, BTreeMap<u8, PathBuf>>) -> String {
+    let mut out = String::new();
+    for (year, days) in years {
+        writeln!(out, "mod y{} {{", year).unwrap();
+        for (day, path) in days {
+            writeln!(out, "    #[path = {:?}]", path.display().to_string()).unwrap();
+            writeln!(out, "    pub mod d{:02};", day).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+    out
+}
+
+/// Invocation of the `registry!` macro listing every day, included by `registry.rs`.
+fn registry(years: &BTreeMap<u16, BTreeMap<u8, PathBuf>>) -> String {
+    let mut out = "registry! {\n".to_string();
+    for (year, days) in years {
+        let days = days.keys().map(|d| format!("{:02}", d)).collect::<Vec<_>>().join(" ");
+        writeln!(out, "    {} => y{} [{}],", year, year, days).unwrap();
+    }
+    out + "}\n"
+}
+
+fn main() -> io::Result<()> {
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").expect("run by cargo")).join("src");
+    let out = PathBuf::from(env::var("OUT_DIR").expect("run by cargo"));
+    // Scanning the whole tree catches new days as well as new years.
+    println!("cargo:rerun-if-changed=src");
+
+    let years = discover(&src)?;
+    fs::write(out.join("years.rs"), modules(&years))?;
+    fs::write(out.join("registry.rs"), registry(&years))
+}
@@ -0,0 +1,158 @@
+use std::{alloc::{GlobalAlloc, Layout, System}, cell::Cell, fmt::Display, sync::atomic::{AtomicBool, Ordering}};
+
+use serde::Serialize;
+
+/// System allocator which also counts allocations once [`enable`]d, the overhead being a single flag check otherwise.
+struct Counting;
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: usize,
+    allocated: usize,
+    // Memory allocated before counting was enabled may be freed afterwards, hence signed counters.
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    // Counted per thread so a phase only accounts for its own allocations, even with other threads running. Built at
+    // compile time without destructor, so the allocator can use it without allocating itself.
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters { allocations: 0, allocated: 0, live: 0, peak: 0 }) };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // The counters are gone while the thread is being torn down, its last allocations being lost anyway.
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        f(&mut c);
+        counters.set(c);
+    });
+}
+
+fn record(allocated: usize, freed: usize) {
+    update(|c| {
+        c.allocations += 1;
+        c.allocated += allocated;
+        c.live += allocated as isize - freed as isize;
+        c.peak = c.peak.max(c.live);
+    });
+}
+
+fn release(freed: usize) {
+    update(|c| c.live -= freed as isize);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        release(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record(new_size, layout.size());
+        }
+        new
+    }
+}
+
+/// Counts allocations until the returned guard is dropped, which restores the previous state.
+pub fn enable() -> Enabled {
+    Enabled(ENABLED.swap(true, Ordering::Relaxed))
+}
+
+/// Keeps allocations counted, see [`enable`].
+#[must_use]
+pub struct Enabled(bool);
+
+impl Drop for Enabled {
+    fn drop(&mut self) {
+        ENABLED.store(self.0, Ordering::Relaxed);
+    }
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Allocations made during a phase, reallocations counting as new ones of the full size.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// Highest amount of memory held at once over what was already held when the phase started.
+    pub peak_bytes: usize,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocations, {} bytes, peak {} bytes", self.allocations, self.bytes, self.peak_bytes)
+    }
+}
+
+/// Allocations of the phases of a part, parsing and preparation being shared with the other part of the run.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Phases {
+    pub parse: Stats,
+    pub prepare: Stats,
+    pub solve: Stats,
+}
+
+/// Runs `f` and counts the allocations it makes on the current thread, nothing being counted unless enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Stats) {
+    let counters = |c: &Cell<Counters>| c.get();
+    let before = COUNTERS.with(counters);
+    COUNTERS.with(|c| c.set(Counters { peak: before.live, ..before }));
+    let res = f();
+    let after = COUNTERS.with(counters);
+    (res, Stats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.allocated - before.allocated,
+        peak_bytes: (after.peak - before.live).max(0) as usize,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counting() {
+        let counting = enable();
+        // Allocations of the tests running concurrently are counted on their own threads.
+        let (v, stats) = measure(|| {
+            let mut v = Vec::<u64>::with_capacity(100);
+            v.extend(0..200);
+            v
+        });
+        drop(counting);
+        assert_eq!(200, v.len());
+        assert_eq!(Stats { allocations: 2, bytes: 2400, peak_bytes: 1600 }, stats);
+        assert_eq!(Stats::default(), measure(|| vec![0_u8; 10]).1);
+    }
+}
@@ -0,0 +1,174 @@
+use std::{fs, io::ErrorKind, path::PathBuf, time::{SystemTime, UNIX_EPOCH}};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::solver::Part;
+
+macro_rules! answers_file {
+    ($year:expr, $day:expr) => { PathBuf::from(format!("answers/{:02}/{:02}.toml", $year % 100, $day)) }
+}
+
+/// Text of an answer or parameter given either as a string or as a number.
+pub fn text(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+/// Known answers of a stored input, either value may be given as a string or a number.
+///
+/// The verdicts of submitted answers are kept as well so the same wrong guess is never sent twice.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_one: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_two: Option<toml::Value>,
+    /// Unix timestamp before which the server refuses new submissions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wait_until: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    submissions: Vec<Submission>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Judgement {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Submission {
+    pub part: Part,
+    pub answer: String,
+    pub judgement: Judgement,
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<String> {
+        match part {
+            Part::One => self.part_one.as_ref(),
+            Part::Two => self.part_two.as_ref(),
+        }.map(text)
+    }
+
+    /// Tells why submitting `answer` is pointless, based on the previous submissions.
+    pub fn reject(&self, part: Part, answer: &str) -> Option<String> {
+        if let Some(known) = self.get(part) {
+            return Some(if known == answer {
+                "already known as the right answer".to_string()
+            } else {
+                format!("the right answer is already known to be {}", known)
+            });
+        }
+        let value = answer.parse::<i128>().ok();
+        self.submissions.iter().filter(|s| s.part == part).find_map(|s| {
+            let bound = s.answer.parse::<i128>().ok();
+            match (s.judgement, value.zip(bound)) {
+                _ if s.answer == answer => Some(format!("already submitted, it was {:?}", s.judgement)),
+                (Judgement::TooHigh, Some((v, b))) if v >= b => Some(format!("{} was already too high", b)),
+                (Judgement::TooLow, Some((v, b))) if v <= b => Some(format!("{} was already too low", b)),
+                _ => None,
+            }
+        }).or_else(|| {
+            let wait = self.wait_until?.checked_sub(now()).filter(|&t| t > 0)?;
+            Some(format!("rate limited, wait {}s before submitting again", wait))
+        })
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, judgement: Judgement) {
+        if judgement == Judgement::Correct {
+            let value = Some(toml::Value::String(answer.to_string()));
+            match part {
+                Part::One => self.part_one = value,
+                Part::Two => self.part_two = value,
+            }
+        }
+        self.submissions.push(Submission { part, answer: answer.to_string(), judgement });
+    }
+
+    pub fn wait(&mut self, seconds: u64) {
+        self.wait_until = Some(now() + seconds);
+    }
+
+    pub fn save(&self, year: u16, day: u8) -> anyhow::Result<()> {
+        let path = answers_file!(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, toml::to_string(self)?).with_context(|| format!("unable to write {}", path.display()))
+    }
+}
+
+/// Loads the known answers of a day, a missing file meaning that nothing is known yet.
+pub fn load(year: u16, day: u8) -> anyhow::Result<Answers> {
+    let path = answers_file!(year, day);
+    match fs::read_to_string(&path) {
+        Ok(content) => toml::from_str(&content).with_context(|| format!("invalid answers file {}", path.display())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e).with_context(|| format!("unable to read {}", path.display())),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<String>, actual: &str) -> Self {
+        match expected {
+            None => Self::Missing,
+            Some(expected) if expected == actual => Self::Pass,
+            Some(expected) => Self::Fail { expected },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_values() -> anyhow::Result<()> {
+        let answers: Answers = toml::from_str("part_one = 42\npart_two = \"co,de,ka,ta\"")?;
+        assert_eq!(Some("42".to_string()), answers.get(Part::One));
+        assert_eq!(Some("co,de,ka,ta".to_string()), answers.get(Part::Two));
+        Ok(())
+    }
+
+    #[test]
+    fn rejected_guesses() {
+        let mut answers = Answers::default();
+        answers.record(Part::One, "100", Judgement::TooHigh);
+        answers.record(Part::One, "10", Judgement::TooLow);
+        answers.record(Part::One, "42", Judgement::Incorrect);
+        assert!(answers.reject(Part::One, "42").is_some());
+        assert!(answers.reject(Part::One, "120").is_some());
+        assert!(answers.reject(Part::One, "5").is_some());
+        assert!(answers.reject(Part::One, "50").is_none());
+        assert!(answers.reject(Part::Two, "42").is_none());
+
+        answers.record(Part::One, "50", Judgement::Correct);
+        assert_eq!(Some("50".to_string()), answers.get(Part::One));
+        assert!(answers.reject(Part::One, "51").is_some());
+    }
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(Verdict::Pass, Verdict::check(Some("42".to_string()), "42"));
+        assert_eq!(Verdict::Fail { expected: "42".to_string() }, Verdict::check(Some("42".to_string()), "0"));
+        assert_eq!(Verdict::Missing, Verdict::check(None, "0"));
+    }
+}
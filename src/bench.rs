@@ -0,0 +1,196 @@
+use std::{collections::HashMap, fmt::Display, fs, time::{Duration, Instant}};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{registry::Day, runner, solver::{Implementation, Part}};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Parse,
+    PartOne,
+    PartTwo,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Phase::Parse => "parse",
+            Phase::PartOne => "one",
+            Phase::PartTwo => "two",
+        })
+    }
+}
+
+/// Timings of a phase, in nanoseconds.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    fn new(samples: &mut [Duration]) -> Self {
+        samples.sort_unstable();
+        let ns = samples.iter().map(|d| d.as_nanos() as f64).collect::<Vec<_>>();
+        let len = ns.len() as f64;
+        let mean = ns.iter().sum::<f64>() / len;
+        let median = if ns.len() % 2 == 0 {
+            (ns[ns.len()/2 - 1] + ns[ns.len()/2]) / 2.
+        } else {
+            ns[ns.len()/2]
+        };
+        Self {
+            min: ns[0],
+            median,
+            mean,
+            stddev: (ns.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / len).sqrt(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Measure {
+    pub year: u16,
+    pub day: u8,
+    pub phase: Phase,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+pub struct Options {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+fn sample(options: &Options, mut f: impl FnMut() -> anyhow::Result<()>) -> anyhow::Result<Stats> {
+    (0..options.warmup).try_for_each(|_| f())?;
+    let mut samples = (0..options.iterations.max(1)).map(|_| {
+        let start = Instant::now();
+        f()?;
+        anyhow::Ok(start.elapsed())
+    }).collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(Stats::new(&mut samples))
+}
+
+/// Benchmarks parsing and the implemented parts of a day, the input being kept in memory so only the solver is
+/// measured. A part failing to find its answer fails the whole day rather than timing its error.
+/// The state shared by the parts is prepared along with the parsing, so parts only account for their own work.
+pub fn bench_day(day: &Day, input: &str, options: &Options) -> anyhow::Result<Vec<Measure>> {
+    let measure = |phase, stats| Measure { year: day.year, day: day.day, phase, stats };
+    let parse = || runner::catch(|| {
+        let problem = (day.parse)(input)?;
+        problem.prepare();
+        Ok(problem)
+    });
+    let problem = parse()?;
+    let mut res = vec![measure(Phase::Parse, sample(options, || parse().map(|_| ()))?)];
+    for (phase, part) in [(Phase::PartOne, Part::One), (Phase::PartTwo, Part::Two)] {
+        // Timing the error of a part which is not there would only be noise in the baseline.
+        if day.implementation[part.index()] == Implementation::Missing {
+            continue;
+        }
+        res.push(measure(phase, sample(options, || runner::catch(|| {
+            problem.solve(part)?;
+            Ok(())
+        }))?));
+    }
+    Ok(res)
+}
+
+fn format_ns(ns: f64) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns as u64))
+}
+
+pub fn load_baseline(path: &str) -> anyhow::Result<HashMap<(u16, u8, Phase), Stats>> {
+    let content = fs::read_to_string(path).with_context(|| format!("unable to read baseline {}", path))?;
+    let measures: Vec<Measure> = serde_json::from_str(&content).with_context(|| format!("invalid baseline {}", path))?;
+    Ok(measures.into_iter().map(|m| ((m.year, m.day, m.phase), m.stats)).collect())
+}
+
+pub fn save_baseline(path: &str, measures: &[Measure]) -> anyhow::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(measures)?).with_context(|| format!("unable to write baseline {}", path))
+}
+
+/// Benchmarks each day, comparing median times with the baseline if any, and counts the phases that got slower than `threshold` percents.
+pub fn bench<'a>(
+    days: impl Iterator<Item = &'a Day>,
+    input: impl Fn(&Day) -> anyhow::Result<String>,
+    options: &Options,
+    baseline: Option<HashMap<(u16, u8, Phase), Stats>>,
+    threshold: f64,
+) -> (Vec<Measure>, usize) {
+    println!("{:<4} {:>3} {:<5} {:>12} {:>12} {:>12} {:>12} {:>9}", "year", "day", "phase", "min", "median", "mean", "stddev", "change");
+    let mut measures = vec![];
+    let mut regressions = 0;
+    for day in days {
+        match input(day).and_then(|input| bench_day(day, &input, options)) {
+            Ok(res) => res.into_iter().for_each(|m| {
+                let change = baseline.as_ref()
+                    .and_then(|b| b.get(&(m.year, m.day, m.phase)))
+                    .map(|old| (m.stats.median - old.median) * 100. / old.median);
+                let flag = match change {
+                    Some(c) if c > threshold => {
+                        regressions += 1;
+                        format!("{:+8.1}% SLOWER", c)
+                    },
+                    Some(c) => format!("{:+8.1}%", c),
+                    None => String::new(),
+                };
+                println!("{:<4} {:>3} {:<5} {:>12} {:>12} {:>12} {:>12} {}", m.year, m.day, m.phase,
+                    format_ns(m.stats.min), format_ns(m.stats.median), format_ns(m.stats.mean), format_ns(m.stats.stddev), flag);
+                measures.push(m);
+            }),
+            Err(e) => println!("{:<4} {:>3} ERROR {:#}", day.year, day.day, e),
+        }
+    }
+    (measures, regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::{self, Answer, Fallible, Solver};
+
+    use super::*;
+
+    /// Day whose first part finds its answer only if told so, the second one being missing.
+    struct Stub(bool);
+
+    impl Solver for Stub {
+        const IMPLEMENTATION: [Implementation; 2] = [Implementation::Complete, Implementation::Missing];
+
+        fn part_one(&self) -> impl Answer {
+            Fallible(if self.0 { Ok(1) } else { Err(solver::Error::NoSolution) })
+        }
+
+        fn part_two(&self) -> impl Answer {
+            Fallible::<u8>(Err(solver::Error::NotImplemented))
+        }
+    }
+
+    fn day(parse: for<'a> fn(&'a str) -> anyhow::Result<Box<dyn solver::DynSolver + 'a>>) -> Day {
+        Day { year: 2015, day: 1, parse, implementation: Stub::IMPLEMENTATION }
+    }
+
+    #[test]
+    fn parts() -> anyhow::Result<()> {
+        let options = Options { warmup: 0, iterations: 2 };
+        let phases = bench_day(&day(|_| Ok(Box::new(Stub(true)))), "", &options)?.into_iter().map(|m| m.phase).collect::<Vec<_>>();
+        assert_eq!(vec![Phase::Parse, Phase::PartOne], phases);
+        let err = bench_day(&day(|_| Ok(Box::new(Stub(false)))), "", &options).unwrap_err();
+        assert_eq!(Some(&solver::Error::NoSolution), err.downcast_ref());
+        Ok(())
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(&mut [4, 1, 3, 2].map(Duration::from_nanos));
+        assert_eq!(1., stats.min);
+        assert_eq!(2.5, stats.median);
+        assert_eq!(2.5, stats.mean);
+        assert_eq!(1.25_f64.sqrt(), stats.stddev);
+    }
+}
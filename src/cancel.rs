@@ -0,0 +1,63 @@
+use std::{cell::RefCell, sync::{atomic::{AtomicBool, Ordering}, Arc}};
+
+use crate::solver;
+
+/// Request to stop shared between the runner and the thread solving a part, once it ran out of time.
+#[derive(Clone, Default, Debug)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the solver should stop, cheap enough to be checked on each iteration of a brute-force loop.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Error of a search which ended without result, telling a cancelled search apart from an exhausted one.
+    pub fn failure(&self) -> solver::Error {
+        if self.is_cancelled() {
+            solver::Error::Timeout
+        } else {
+            solver::Error::NoSolution
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Token> = RefCell::default();
+}
+
+/// Makes `token` the one returned by [`token`] on the current thread.
+pub fn install(token: Token) {
+    CURRENT.with(|current| *current.borrow_mut() = token);
+}
+
+/// Token of the solver running on the current thread, never cancelled when it runs without time limit. Meant to be
+/// fetched once before a long loop.
+pub fn token() -> Token {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn installed_per_thread() {
+        let runner = Token::default();
+        let worker = runner.clone();
+        runner.cancel();
+        let (cancelled, failure) = thread::spawn(move || {
+            install(worker);
+            (token().is_cancelled(), token().failure())
+        }).join().unwrap();
+        assert!(cancelled);
+        assert_eq!(solver::Error::Timeout, failure);
+        assert!(!token().is_cancelled());
+    }
+}
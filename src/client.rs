@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use regex::Regex;
+
+use crate::{answers::Judgement, config::Config, solver::Part};
+
+const USER_AGENT: &str = concat!("github.com/max4t/advent-of-code v", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Reply {
+    Judged(Judgement, Option<Duration>),
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl Reply {
+    /// Interprets the page returned once an answer is posted.
+    pub fn parse(body: &str) -> anyhow::Result<Self> {
+        let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")?;
+        let retry = Regex::new(r"(?i)please wait (one|\d+) minutes? before trying again")?;
+        let seconds = |c: Option<regex::Match>| c.map_or(0, |m| m.as_str().parse::<u64>().unwrap_or(1));
+        let wait = retry.captures(body).map(|c| Duration::from_secs(60 * c.get(1).map_or(1, |m| m.as_str().parse().unwrap_or(1))));
+
+        Ok(if body.contains("That's the right answer") {
+            Self::Judged(Judgement::Correct, None)
+        } else if body.contains("That's not the right answer") {
+            Self::Judged(if body.contains("your answer is too high") {
+                Judgement::TooHigh
+            } else if body.contains("your answer is too low") {
+                Judgement::TooLow
+            } else {
+                Judgement::Incorrect
+            }, wait)
+        } else if let Some(c) = left.captures(body) {
+            Self::RateLimited(Duration::from_secs(seconds(c.get(1)) * 60 + seconds(c.get(2))))
+        } else if body.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else {
+            anyhow::bail!("unexpected answer page")
+        })
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: config.base_url().to_string(),
+            session: config.session()?.to_string(),
+        })
+    }
+
+    fn send(&self, method: &str, path: &str, form: Option<&[(&str, &str)]>) -> anyhow::Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+        let req = self.agent.request(method, &url).set("Cookie", &format!("session={}", self.session));
+        let res = match form {
+            Some(form) => req.send_form(form),
+            None => req.call(),
+        };
+        match res {
+            Ok(res) => Ok(res.into_string()?),
+            Err(ureq::Error::Status(code, res)) => Err(anyhow!("{} responded with {}: {}", url, code, res.into_string().unwrap_or_default().trim())),
+            Err(e) => Err(e).with_context(|| format!("unable to reach {}", url)),
+        }
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> anyhow::Result<String> {
+        self.send("GET", &format!("/{}/day/{}/input", year, day), None)
+    }
+
+    pub fn submit(&self, year: u16, day: u8, part: Part, answer: &str) -> anyhow::Result<Reply> {
+        let level = match part {
+            Part::One => "1",
+            Part::Two => "2",
+        };
+        Reply::parse(&self.send("POST", &format!("/{}/day/{}/answer", year, day), Some(&[("level", level), ("answer", answer)]))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::{BufRead, BufReader, Read, Write}, net::TcpListener, thread};
+
+    use super::*;
+
+    /// Serves a single request with the given status and body, the handle yielding the received request head
+    /// followed by its content.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = (&mut reader).lines().map_while(Result::ok).take_while(|l| !l.is_empty()).collect::<Vec<_>>();
+            let length = head.iter()
+                .find_map(|h| h.to_lowercase().strip_prefix("content-length: ").and_then(|l| l.parse::<u64>().ok()))
+                .unwrap_or_default();
+            let mut content = String::new();
+            (&mut reader).take(length).read_to_string(&mut content).unwrap();
+            head.push(content);
+            write!(reader.get_mut(), "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+            head
+        });
+        (url, handle)
+    }
+
+    fn client(base_url: String) -> Client {
+        Client::new(&Config { session: Some("secret".to_string()), base_url: Some(base_url) }).unwrap()
+    }
+
+    #[test]
+    fn input() -> anyhow::Result<()> {
+        let (url, server) = serve_once("200 OK", "1\n2\n");
+        assert_eq!("1\n2\n", client(url).input(2024, 5)?);
+        let head = server.join().unwrap();
+        assert_eq!("GET /2024/day/5/input HTTP/1.1", head[0]);
+        assert!(head.iter().any(|h| h.eq_ignore_ascii_case("cookie: session=secret")));
+        assert!(head.iter().any(|h| h.to_lowercase().starts_with("user-agent: github.com/max4t/advent-of-code")));
+        Ok(())
+    }
+
+    #[test]
+    fn submit() -> anyhow::Result<()> {
+        let (url, server) = serve_once("200 OK", "<article><p>That's the right answer!  You are one gold star closer.</p></article>");
+        assert_eq!(Reply::Judged(Judgement::Correct, None), client(url).submit(2024, 5, Part::Two, "123")?);
+        let head = server.join().unwrap();
+        assert_eq!("POST /2024/day/5/answer HTTP/1.1", head[0]);
+        assert_eq!(Some(&"level=2&answer=123".to_string()), head.last());
+        Ok(())
+    }
+
+    #[test]
+    fn replies() -> anyhow::Result<()> {
+        assert_eq!(
+            Reply::Judged(Judgement::TooHigh, Some(Duration::from_secs(60))),
+            Reply::parse("<p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p>")?,
+        );
+        assert_eq!(
+            Reply::Judged(Judgement::TooLow, Some(Duration::from_secs(300))),
+            Reply::parse("<p>That's not the right answer; your answer is too low.  please wait 5 minutes before trying again.</p>")?,
+        );
+        assert_eq!(Reply::Judged(Judgement::Incorrect, None), Reply::parse("<p>That's not the right answer.</p>")?);
+        assert_eq!(Reply::RateLimited(Duration::from_secs(90)), Reply::parse("<p>You gave an answer too recently. You have 1m 30s left to wait.</p>")?);
+        assert_eq!(Reply::RateLimited(Duration::from_secs(42)), Reply::parse("<p>You gave an answer too recently. You have 42s left to wait.</p>")?);
+        assert_eq!(Reply::AlreadySolved, Reply::parse("<p>You don't seem to be solving the right level.  Did you already complete it?</p>")?);
+        assert!(Reply::parse("<p>???</p>").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn missing_input() {
+        let (url, server) = serve_once("404 Not Found", "not yet");
+        assert!(client(url).input(2024, 26).is_err());
+        server.join().unwrap();
+    }
+}
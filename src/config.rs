@@ -0,0 +1,40 @@
+use std::{env, fs, io::ErrorKind};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings read from `aoc.toml` (or the file pointed by `AOC_CONFIG`), each of them being overridable by its
+/// `AOC_*` environment variable.
+#[derive(Deserialize, Default, Debug)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl Config {
+    pub fn load() -> anyhow::Result<Self> {
+        let path = env::var("AOC_CONFIG").unwrap_or_else(|_| "aoc.toml".to_string());
+        let mut config: Self = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).with_context(|| format!("invalid config file {}", path))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e).with_context(|| format!("unable to read {}", path)),
+        };
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+        Ok(config)
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL).trim_end_matches('/')
+    }
+
+    pub fn session(&self) -> anyhow::Result<&str> {
+        self.session.as_deref().context("missing session token, set AOC_SESSION or `session` in aoc.toml")
+    }
+}
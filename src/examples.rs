@@ -0,0 +1,117 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::PathBuf};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{answers, solver::Part};
+
+macro_rules! examples_dir {
+    ($year:expr, $day:expr) => { PathBuf::from(format!("examples/puzzles/{:02}/{:02}", $year % 100, $day)) }
+}
+
+/// Expected answers of an example along with the parameters it needs, read from `<number>.toml` next to the
+/// `<number>` input file. Other fields, such as the submissions kept along with the answers of real inputs, are
+/// rejected.
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    part_one: Option<toml::Value>,
+    part_two: Option<toml::Value>,
+    #[serde(default)]
+    params: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Debug)]
+pub struct Example {
+    pub number: usize,
+    pub input: PathBuf,
+    pub expected: Expected,
+}
+
+impl Example {
+    pub fn get(&self, part: Part) -> Option<String> {
+        match part {
+            Part::One => self.expected.part_one.as_ref(),
+            Part::Two => self.expected.part_two.as_ref(),
+        }.map(answers::text)
+    }
+
+    /// Parts among `parts` that the example gives an answer for, all of them if it gives none.
+    pub fn parts(&self, parts: &[Part]) -> Vec<Part> {
+        let known = parts.iter().copied().filter(|&p| self.get(p).is_some()).collect::<Vec<_>>();
+        if known.is_empty() { parts.to_vec() } else { known }
+    }
+
+    /// Parameter overrides, as given to `run --param`.
+    pub fn params(&self) -> Vec<(String, String)> {
+        self.expected.params.iter().map(|(name, value)| (name.clone(), answers::text(value))).collect()
+    }
+}
+
+/// Loads the stored examples of a day ordered by number, a missing directory meaning that there is none.
+pub fn load(year: u16, day: u8) -> anyhow::Result<Vec<Example>> {
+    let dir = examples_dir!(year, day);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e).with_context(|| format!("unable to read {}", dir.display())),
+    };
+    let mut examples = entries.map(|entry| {
+        let path = entry?.path();
+        let Some(number) = path.file_name().and_then(|n| n.to_str()).and_then(|n| n.parse::<usize>().ok()) else {
+            return anyhow::Ok(None);
+        };
+        let answers = path.with_extension("toml");
+        let expected = match fs::read_to_string(&answers) {
+            Ok(content) => toml::from_str(&content).with_context(|| format!("invalid example file {}", answers.display()))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Expected::default(),
+            Err(e) => return Err(e).with_context(|| format!("unable to read {}", answers.display())),
+        };
+        Ok(Some(Example { number, input: path, expected }))
+    }).filter_map(Result::transpose).collect::<anyhow::Result<Vec<_>>>()?;
+    examples.sort_by_key(|e| e.number);
+    Ok(examples)
+}
+
+/// Finds the example `number` of a day.
+pub fn find(year: u16, day: u8, number: usize) -> anyhow::Result<Example> {
+    load(year, day)?.into_iter().find(|e| e.number == number)
+        .with_context(|| format!("no example {} stored in {}", number, examples_dir!(year, day).display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{registry, runner};
+
+    use super::*;
+
+    #[test]
+    fn parts() -> anyhow::Result<()> {
+        let example = |toml| anyhow::Ok(Example { number: 1, input: PathBuf::new(), expected: toml::from_str(toml)? });
+        let only_one = example("part_one = 12\n[params]\nsize = \"11,7\"")?;
+        assert_eq!(Some("12".to_string()), only_one.get(Part::One));
+        assert_eq!(vec![Part::One], only_one.parts(&Part::ALL));
+        assert_eq!(vec![Part::Two], only_one.parts(&[Part::Two]));
+        assert_eq!(vec![("size".to_string(), "11,7".to_string())], only_one.params());
+        assert!(example("part_one = 12\nwait_until = 3").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn stored_examples() -> anyhow::Result<()> {
+        let mut checked = 0;
+        for day in registry::DAYS {
+            for example in load(day.year, day.day)? {
+                let parts = Part::ALL.into_iter().filter(|&p| example.get(p).is_some()).collect::<Vec<_>>();
+                let input = example.input.to_string_lossy();
+                for record in runner::run_day(day, &input, &example.params(), &parts, None) {
+                    let answer = record.answer.with_context(|| format!("{} day {} example {}", day.year, day.day, example.number))?;
+                    assert_eq!(example.get(record.part), Some(answer), "{} day {} example {} part {}", day.year, day.day, example.number, record.part);
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 0);
+        Ok(())
+    }
+}
@@ -1,198 +1,320 @@
-use std::{fs, io::{self, BufRead}};
+use std::{fs, path::Path, process::ExitCode, time::Duration};
+use anyhow::anyhow;
 use rand::prelude::*;
 
-use clap::{Args, Parser, Subcommand, ValueEnum};
-use solver::Solver;
+use clap::{Args, Parser, Subcommand};
+use report::Format;
+use solver::{Part, Parts};
 
 mod solver;
+mod allocs;
+mod cancel;
+mod answers;
+mod examples;
+mod bench;
+mod config;
+mod client;
+mod registry;
+mod runner;
+mod report;
+mod scaffold;
+mod progress;
 mod map;
+mod parse;
+mod trace;
 mod game;
-mod y2015;
-mod y2016;
-mod y2017;
-mod y2018;
-mod y2019;
-mod y2023;
-mod y2024;
+
+// Years and their days, declared from the files found in `src/y20XX/dNN.rs` by the build script.
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
 
 macro_rules! default_file {
-    ($year:expr, $day:expr) => { format!("examples/inputs/{}/{}", $year, $day) }
+    ($year:expr, $day:expr) => { format!("examples/inputs/{:02}/{:02}", $year % 100, $day) }
 }
 
 #[derive(Subcommand)]
 enum Command {
-    #[command(about = "Execute a specific day")]
+    #[command(about = "Execute a specific day, a whole year or every registered day")]
     Run(Run),
+    #[command(about = "Check solvers against the known answers of their stored input")]
+    Verify(Verify),
+    #[command(about = "Benchmark parsing and both parts of each day")]
+    Bench(Bench),
+    #[command(about = "Download the input of a day into its default location")]
+    Fetch(Fetch),
+    #[command(about = "Compute the answer of a day then submit it")]
+    Submit(Submit),
+    #[command(about = "Generate and register the module of a new day")]
+    New(New),
     #[command(about = "Randomly choose the next available challenge")]
     Pick,
-    #[command(about = "Display progress per year")]
-    Progress,
+    #[command(about = "Display progress per year along with a calendar of each part status")]
+    Progress(Progress),
 }
 
 #[derive(Args)]
 struct Run {
-    #[arg(help = "year of the exercise")]
-    year: String,
+    #[arg(help = "year of the exercise", value_parser = parse_year, required_unless_present = "all")]
+    year: Option<u16>,
+    #[arg(help = "day of the year (run both parts of every day of the year when omitted)")]
+    day: Option<u8>,
+    #[arg(value_enum, help = "part one, two or both parts of the day", default_value_t = Parts::Both)]
+    parts: Parts,
+    #[arg(short, help = "input file (use - for stdin) [default: examples/inputs/<year>/<day>]", requires = "day")]
+    file: Option<String>,
+    #[arg(long, help = "run the stored puzzle example N of the day with its parameters", value_name = "N", requires = "day", conflicts_with = "file")]
+    example: Option<usize>,
+    #[arg(long, help = "run both parts of every registered day", conflicts_with = "year")]
+    all: bool,
+    #[arg(long, value_enum, help = "output format", default_value_t = Format::Table)]
+    format: Format,
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param, help = "override a parameter of the day", requires = "day")]
+    params: Vec<(String, String)>,
+    #[arg(long, help = "count allocations of parsing, preparing and solving each part")]
+    alloc_stats: bool,
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout, help = "report parts not solved in time as TIMEOUT")]
+    timeout: Option<Duration>,
+}
+
+#[derive(Args)]
+struct Verify {
+    #[arg(help = "only verify this year", value_parser = parse_year)]
+    year: Option<u16>,
+    #[arg(help = "only verify this day of the year", requires = "year")]
+    day: Option<u8>,
+    #[arg(long, value_enum, help = "output format", default_value_t = Format::Table)]
+    format: Format,
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout, help = "report parts not solved in time as TIMEOUT")]
+    timeout: Option<Duration>,
+}
+
+#[derive(Args)]
+struct Bench {
+    #[arg(help = "only benchmark this year", value_parser = parse_year)]
+    year: Option<u16>,
+    #[arg(help = "only benchmark this day of the year", requires = "year")]
+    day: Option<u8>,
+    #[arg(short = 'n', long, help = "measured runs per phase", default_value_t = 10)]
+    iterations: usize,
+    #[arg(long, help = "unmeasured runs per phase", default_value_t = 1)]
+    warmup: usize,
+    #[arg(long, help = "save the results as a JSON baseline")]
+    save: Option<String>,
+    #[arg(long, help = "compare the results with a JSON baseline")]
+    baseline: Option<String>,
+    #[arg(long, help = "median slowdown (in %) over the baseline that fails the benchmark", default_value_t = 10.)]
+    threshold: f64,
+}
+
+#[derive(Args)]
+struct Fetch {
+    #[arg(help = "year of the exercise", value_parser = parse_year)]
+    year: u16,
     #[arg(help = "day of the year")]
-    day: String,
-    #[arg(help = "part one or two of the day", default_value_t=Part::One)]
+    day: u8,
+    #[arg(long, help = "download again even if the input is already cached")]
+    force: bool,
+}
+
+#[derive(Args)]
+struct Submit {
+    #[arg(help = "year of the exercise", value_parser = parse_year)]
+    year: u16,
+    #[arg(help = "day of the year")]
+    day: u8,
+    #[arg(help = "part one or two of the day")]
     part: Part,
-    #[arg(short, help = "input file (use - for stdin)", default_value_t=default_file!("{year}", "{day}"))]
-    file: String,
+    #[arg(short, help = "input file (use - for stdin) [default: examples/inputs/<year>/<day>]")]
+    file: Option<String>,
+}
+
+#[derive(Args)]
+struct New {
+    #[arg(help = "year of the exercise", value_parser = parse_year)]
+    year: u16,
+    #[arg(help = "day of the year")]
+    day: u8,
+}
+
+#[derive(Args)]
+struct Progress {
+    #[arg(long, help = "run implemented parts to check them against their known answers")]
+    verify: bool,
 }
 
 #[derive(Parser)]
 struct Cli {
-    // #[arg(help = "year of the exercise")]
-    // year: String,
-    // #[arg(help = "day of the year")]
-    // day: String,
-    // #[arg(help = "part one or two of the day", default_value_t=Part::One)]
-    // part: Part,
-    // #[arg(short, help = "input file (use - for stdin)", default_value_t=default_file!("{year}", "{day}"))]
-    // file: String,
     #[command(subcommand)]
     command: Command,
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, help = "print debug output of solvers to stderr (-vv for more details)")]
+    verbose: u8,
 }
 
-#[derive(ValueEnum, Clone, Debug)]
-enum Part {
-    #[value(help = "compute part one")]
-    One,
-    #[value(help = "compute part two")]
-    Two,
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    let (name, value) = s.split_once('=').ok_or_else(|| format!("expected NAME=VALUE instead of {}", s))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    Duration::try_from_secs_f64(s.parse::<f64>().map_err(|e| e.to_string())?).map_err(|e| e.to_string())
+}
+
+fn parse_year(s: &str) -> Result<u16, String> {
+    let year = s.parse::<u16>().map_err(|e| e.to_string())?;
+    Ok(if year < 100 { 2000 + year } else { year })
+}
+
+/// Exit status telling which kind of failure stopped the command, clap using 2 for invalid arguments.
+fn exit_code(e: &anyhow::Error) -> u8 {
+    e.chain().find_map(|cause| {
+        cause.downcast_ref::<solver::Error>().map(|e| match e {
+            solver::Error::InvalidInput(_) => 3,
+            solver::Error::NoSolution => 4,
+            solver::Error::NotImplemented => 5,
+            solver::Error::Timeout => 7,
+        })
+        .or_else(|| cause.downcast_ref::<parse::Error>().map(|_| 3))
+        .or_else(|| cause.downcast_ref::<runner::Panic>().map(|_| 6))
+    }).unwrap_or(1)
 }
 
-impl ToString for Part {
-    fn to_string(&self) -> String {
-        match self {
-            Part::One => "one",
-            Part::Two => "two",
-        }.to_string()
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::from(exit_code(&e))
+        },
     }
 }
 
-macro_rules! case {
-    ($args:ident $t:literal -> ) => {
-    };
-    ($args:ident $t:literal -> $s1:literal-$s2:literal) => {
-        seq_macro::seq!(N in $s1..=$s2 {
-            match $args.day.as_str() {
-                #(
-                    stringify!(N) => {
-                        paste::paste! {
-                            let ex: [<y20 $t>]::d~N::Problem = if $args.file == "-" {
-                                io::stdin().lines().try_into()?
-                            } else {
-                                let input = if $args.file == default_file!("{year}", "{day}") {
-                                    default_file!($args.year, $args.day)
-                                } else {
-                                    $args.file
-                                };
-                                fs::read(input)?.lines().try_into()?
-                            };
-                        }
-                        match $args.part {
-                            Part::One => println!("Result: {}", ex.part_one()),
-                            Part::Two => println!("Result: {}", ex.part_two()),
-                        };
-                    },
-                )*
-                _ => panic!("unknown day"),
+fn run(args: Cli) -> anyhow::Result<()> {
+    trace::set_verbosity(args.verbose);
+    let _counting = matches!(args.command, Command::Run(Run { alloc_stats: true, .. })).then(allocs::enable);
+    match args.command {
+        Command::Run(Run { year: Some(year), day: Some(day), parts, file, example, format, params, timeout, .. }) => {
+            let day = registry::find(year, day)
+                .ok_or_else(|| anyhow!("no solver registered for {} day {}", year, day))?;
+            let (file, params, example) = match example {
+                Some(number) => {
+                    let example = examples::find(year, day.day, number)?;
+                    let overrides = example.params().into_iter().chain(params).collect::<Vec<_>>();
+                    (example.input.to_string_lossy().into_owned(), overrides, Some(example))
+                },
+                None => (file.unwrap_or_else(|| default_file!(year, day.day)), params, None),
+            };
+            // An example only checks the parts it gives an answer for, the other one may not even apply to it.
+            let run = example.as_ref().map_or_else(|| parts.parts().to_vec(), |e| e.parts(parts.parts()));
+            let records = runner::quiet_panics(|| runner::run_day(day, &file, &params, &run, timeout));
+            let expected = |part| example.as_ref().and_then(|e| e.get(part));
+            // An unimplemented part is only a failure when explicitly asked for.
+            let skipped = |record: &runner::Record| parts == Parts::Both && record.unimplemented();
+            if format == Format::Table {
+                for record in records.iter().filter(|r| r.answer.is_ok() || skipped(r)) {
+                    let label = if parts == Parts::Both { format!("Part {}", record.part) } else { "Result".to_string() };
+                    match &record.answer {
+                        Ok(answer) if record.implementation == solver::Implementation::Hardcoded => println!("{}: {} (hardcoded)", label, answer),
+                        Ok(answer) => println!("{}: {}", label, answer),
+                        Err(_) => println!("{}: not implemented", label),
+                    }
+                    if let Some(expected) = expected(record.part) {
+                        println!("Expected: {}", expected);
+                    }
+                    if let Some(allocs) = record.allocs {
+                        println!("Allocations: parse {}; prepare {}; solve {}", allocs.parse, allocs.prepare, allocs.solve);
+                    }
+                }
+            } else {
+                report::emit(format, &records.iter().map(|r| report::Row::new(r, expected(r.part))).collect::<Vec<_>>())?;
             }
-        })
-    };
-}
-macro_rules! available_years {
-    ($([$t:literal $($s2:tt)?])*) => {
-        available_years!([], $([$t $($s2)?])*)
-    };
-    ([$($year:literal,)*], ) => {
-        [$($year,)*]
-    };
-    ([$($year:literal,)*], [$_:literal 25] $([$t:literal $($s2:tt)?])*) => {
-        available_years!([$($year,)*], $([$t $($s2)?])*)
-    };
-    ([$($year:literal,)*], [$t:literal $($s2:tt)?] $([$ts:literal $($s2s:tt)?])*) => {
-        available_years!([$($year,)* $t,], $([$ts $($s2s)?])*)
-    };
-}
-macro_rules! cases {
-    ($args:ident $($t:literal -> $($s1:literal-$s2:tt)?,)*) => (
-        match $args.command {
-            Command::Run(run) => match run.year.as_str() {
-                $(
-                    stringify!($t) => {
-                        case!(run $t -> $($s1-$s2)?);
-                    },
-                )*
-                _ => panic!("unknown year"),
-            },
-            Command::Pick => {
-                let years = available_years!($([$t $($s2)?])*);
-                let mut rng = rand::thread_rng();
-                let year = years.choose(&mut rng).unwrap();
-                println!("Go to https://adventofcode.com/20{}/day/{}", year, match year.to_string().as_str() {
-                    $(
-                        stringify!($t) => match stringify!($($s2)?).trim_start_matches('0') {
-                            "" => "0",
-                            e => e,
-                        }.parse::<u8>().unwrap() + 1,
-                    )*
-                    _ => panic!("unknown year"),
-                });
-            },
-            Command::Progress => {
-                $(
-                    let progress = match stringify!($($s2)?).trim_start_matches('0') {
-                        "" => "0",
-                        e => e,
-                    }.parse::<usize>().unwrap()*200/50;
-                    println!("20{}   {} {:3}%", $t, {
-                        let p = progress*SIZE*(CODE.len())/100;
-                        (0..SIZE).into_iter().map(|l| p.saturating_sub(l*CODE.len()).min(CODE.len() - 1)).map(|l| CODE[l as usize]).collect::<String>()
-                    }, progress);
-                )*
-                let progress = [$(match stringify!($($s2)?).trim_start_matches('0') {
-                    "" => "0",
-                    e => e,
-                }.parse::<usize>().unwrap(),)*];
-                let len = progress.len();
-                let progress = progress.iter().sum::<usize>()*4/len;
-                println!("Global {} {:3}%", {
-                    let p = progress*SIZE*CODE.len()/100;
-                    (0..SIZE).into_iter().map(|l| p.saturating_sub(l*CODE.len()).min(CODE.len() - 1)).map(|l| CODE[l as usize]).collect::<String>()
-                }, progress);
-                println!("");
-                (0..=100).for_each(|l| {
-                    println!("{}", {
-                        let p = l*SIZE*CODE.len()/100;
-                        (0..SIZE).into_iter().map(|l| p.saturating_sub(l*CODE.len()).min(CODE.len() - 1)).map(|l| CODE[l as usize]).collect::<String>()
-                    });    
-                });
+            let failure = records.into_iter().filter(|r| !skipped(r)).find_map(|r| r.answer.err().map(|e| (r.part, e)));
+            if let Some((part, e)) = failure {
+                return Err(e.context(format!("unable to solve {} day {} part {}", year, day.day, part)));
+            }
+        },
+        Command::Run(Run { year, format, timeout, .. }) => {
+            let days = registry::DAYS.iter().filter(|d| year.is_none_or(|y| d.year == y));
+            runner::quiet_panics(|| runner::batch(days, |d| default_file!(d.year, d.day), format, timeout))?;
+        },
+        Command::Verify(Verify { year, day, format, timeout }) => {
+            let days = registry::DAYS.iter()
+                .filter(|d| year.is_none_or(|y| d.year == y) && day.is_none_or(|n| d.day == n));
+            runner::quiet_panics(|| runner::verify(days, |d| default_file!(d.year, d.day), format, timeout))?;
+        },
+        Command::Bench(args) => {
+            let days = registry::DAYS.iter()
+                .filter(|d| args.year.is_none_or(|y| d.year == y) && args.day.is_none_or(|n| d.day == n));
+            let baseline = args.baseline.as_deref().map(bench::load_baseline).transpose()?;
+            let options = bench::Options { warmup: args.warmup, iterations: args.iterations };
+            let (measures, regressions) = runner::quiet_panics(|| {
+                bench::bench(days, |d| runner::read(&default_file!(d.year, d.day)), &options, baseline, args.threshold)
+            });
+            if let Some(path) = args.save {
+                bench::save_baseline(&path, &measures)?;
+            }
+            if regressions > 0 {
+                anyhow::bail!("{} phases got more than {}% slower", regressions, args.threshold);
+            }
+        },
+        Command::Fetch(Fetch { year, day, force }) => {
+            let file = default_file!(year, day);
+            if !force && Path::new(&file).exists() {
+                println!("Using cached {}", file);
+                return Ok(());
+            }
+            let input = client::Client::new(&config::Config::load()?)?.input(year, day)?;
+            if let Some(dir) = Path::new(&file).parent() {
+                fs::create_dir_all(dir)?;
             }
-        }
-    );
-}
-
-const SIZE: usize = 30;
-const CODE: [char; 7] = ['\u{28C0}', '\u{28C4}', '\u{28C6}',  '\u{28C7}', '\u{28E7}', '\u{28F7}', '\u{28FF}'];
-
-fn main() -> anyhow::Result<()> {
-    let args = Cli::parse();
-
-    cases!(args
-        15 -> 01-25,
-        16 -> 01-04,
-        17 -> 01-02,
-        18 -> 01-03,
-        19 -> 01-01,
-        20 -> ,
-        21 -> ,
-        22 -> ,
-        23 -> 01-01,
-        24 -> 01-25,
-    );
+            fs::write(&file, input)?;
+            println!("Saved {}", file);
+        },
+        Command::Submit(Submit { year, day, part, file }) => {
+            let day = registry::find(year, day)
+                .ok_or_else(|| anyhow!("no solver registered for {} day {}", year, day))?;
+            let input = runner::read(&file.unwrap_or_else(|| default_file!(year, day.day)))?;
+            let answer = runner::quiet_panics(|| runner::catch(|| Ok((day.parse)(&input)?.solve(part)?)))?;
+            println!("Answer: {}", answer);
+
+            let mut answers = answers::load(year, day.day)?;
+            if let Some(reason) = answers.reject(part, &answer) {
+                anyhow::bail!("not submitted, {}", reason);
+            }
+            let client = client::Client::new(&config::Config::load()?)?;
+            match client.submit(year, day.day, part, &answer)? {
+                client::Reply::Judged(judgement, wait) => {
+                    answers.record(part, &answer, judgement);
+                    if let Some(wait) = wait {
+                        answers.wait(wait.as_secs());
+                    }
+                    answers.save(year, day.day)?;
+                    println!("Verdict: {:?}", judgement);
+                },
+                client::Reply::RateLimited(wait) => {
+                    answers.wait(wait.as_secs());
+                    answers.save(year, day.day)?;
+                    anyhow::bail!("rate limited, wait {}s before submitting again", wait.as_secs());
+                },
+                client::Reply::AlreadySolved => println!("This part is already solved"),
+            }
+        },
+        Command::New(New { year, day }) => {
+            println!("Created {}", scaffold::scaffold(Path::new("."), year, day)?.display());
+        },
+        Command::Pick => {
+            let years = registry::YEARS.filter(|&y| registry::missing_days(y).next().is_some()).collect::<Vec<_>>();
+            let mut rng = rand::thread_rng();
+            let year = *years.choose(&mut rng).ok_or_else(|| anyhow!("every challenge is already solved"))?;
+            println!("Go to https://adventofcode.com/{}/day/{}", year, registry::missing_days(year).next().unwrap_or_default());
+        },
+        Command::Progress(Progress { verify }) => {
+            let years = runner::quiet_panics(|| registry::YEARS.map(|year| if verify {
+                progress::Year::new(year, |d| progress::verified(d, |d| default_file!(d.year, d.day)))
+            } else {
+                progress::Year::new(year, progress::declared)
+            }).collect::<Vec<_>>());
+            progress::print(&years);
+        },
+    }
 
     Ok(())
 }
@@ -1,20 +1,34 @@
-use std::ops::{Add, Bound, Index, IndexMut, Range};
+use std::ops::{Add, Bound, Index, IndexMut, Mul, Range};
 use std::hash::Hash;
 use anyhow::bail;
 
+use crate::parse::{self, Span};
+
 #[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
 pub struct Pt<T>(pub T, pub T);
 
 impl Pt<usize> {
-    pub fn saturating_add(self, dir: Direction, within: Self) -> Self {
+    /// Next point in the direction `dir`, unless it would go past the top or left edge where `+` underflows.
+    pub fn checked_add(self, dir: Direction) -> Option<Self> {
         let Pt(x, y) = self;
-        match dir {
-            Direction::N if y == 0 => self,
-            Direction::E if x == within.0 - 1 => self,
-            Direction::S if y == within.1 - 1 => self,
-            Direction::W if x == 0 => self,
-            _ => self + dir,
-        }
+        Some(match dir {
+            Direction::N => Pt(x, y.checked_sub(1)?),
+            Direction::E => Pt(x.checked_add(1)?, y),
+            Direction::S => Pt(x, y.checked_add(1)?),
+            Direction::W => Pt(x.checked_sub(1)?, y),
+        })
+    }
+
+    pub fn saturating_add(self, dir: Direction, within: Self) -> Self {
+        self.checked_add(dir).filter(|&Pt(x, y)| x < within.0 && y < within.1).unwrap_or(self)
+    }
+}
+
+impl Pt<i64> {
+    /// Moves by `offset` on a torus of the given size, leaving it on one side coming back on the other. Only for
+    /// signed points, so that moving backwards does not overflow.
+    pub fn torus_add(self, offset: Self, size: Self) -> Self {
+        Self((self.0 + offset.0).rem_euclid(size.0), (self.1 + offset.1).rem_euclid(size.1))
     }
 }
 
@@ -30,7 +44,7 @@ pub enum Side {
     Right,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
     N,
     E,
@@ -38,6 +52,86 @@ pub enum Direction {
     W,
 }
 
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// Opposite direction, when turning around.
+    #[allow(dead_code)]
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::N => Direction::S,
+            Direction::E => Direction::W,
+            Direction::S => Direction::N,
+            Direction::W => Direction::E,
+        }
+    }
+
+    /// Every direction, clockwise from this one.
+    #[allow(dead_code)]
+    pub fn clockwise(self) -> impl Iterator<Item = Self> {
+        std::iter::successors(Some(self), |&dir| Some(dir + Side::Right)).take(4)
+    }
+
+    /// Offset to the cell in this direction, `y` growing southwards.
+    pub fn delta(self) -> (isize, isize) {
+        Direction8::from(self).delta()
+    }
+}
+
+/// Direction of one of the 8 cells around another, diagonals included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N, Direction8::NE, Direction8::E, Direction8::SE, Direction8::S, Direction8::SW, Direction8::W, Direction8::NW,
+    ];
+    pub const DIAGONALS: [Direction8; 4] = [Direction8::NE, Direction8::SE, Direction8::SW, Direction8::NW];
+
+    /// Offset to the cell in this direction, `y` growing southwards.
+    pub const fn delta(self) -> (isize, isize) {
+        // Looked up rather than matched, which is cheaper on the hot path of the neighbour iterators.
+        const DELTAS: [(isize, isize); 8] = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+        DELTAS[self as usize]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::N => Direction8::N,
+            Direction::E => Direction8::E,
+            Direction::S => Direction8::S,
+            Direction::W => Direction8::W,
+        }
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Direction8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Direction8::N => Direction::N,
+            Direction8::E => Direction::E,
+            Direction8::S => Direction::S,
+            Direction8::W => Direction::W,
+            _ => bail!("{:?} is a diagonal direction", value),
+        })
+    }
+}
+
 impl Add<Side> for Direction {
     type Output = Direction;
 
@@ -63,16 +157,17 @@ impl Add<Side> for Direction {
     }
 }
 
+/// Reads a direction written as an arrow `^>v<`, a move `UDLR` or a cardinal point `NESW`.
 impl TryFrom<char> for Direction {
     type Error = anyhow::Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         Ok(match value {
-            '^' => Self::N,
-            '>' => Self::E,
-            'v' => Self::S,
-            '<' => Self::W,
-            _ => bail!("unknown direction"),
+            '^' | 'U' | 'N' => Self::N,
+            '>' | 'R' | 'E' => Self::E,
+            'v' | 'D' | 'S' => Self::S,
+            '<' | 'L' | 'W' => Self::W,
+            _ => bail!("unknown direction {:?}", value),
         })
     }
 }
@@ -104,6 +199,15 @@ macro_rules! add_impl {
             }
         }
 
+        impl Mul<$t> for Pt<$t> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: $t) -> Self::Output {
+                Self(self.0*rhs, self.1*rhs)
+            }
+        }
+
         impl Pt<$t> {
             #[allow(dead_code)]
             pub fn manhattan_distance(self, other: Self) -> $ut {
@@ -115,16 +219,59 @@ macro_rules! add_impl {
 
 add_impl! { usize, unsigned = usize u8, unsigned = u8 u16, unsigned = u16 u32, unsigned = u32 u64, unsigned = u64 u128, unsigned = u128 isize, unsigned = usize i8, unsigned = u8 i16, unsigned = u16 i32, unsigned = u32 i64, unsigned = u64 i128, unsigned = u128 }
 
+/// Rectangular grid stored row after row in a single vector, `Pt(x, y)` being the cell of column `x` in row `y`.
 #[derive(Clone, Debug)]
 pub struct Grid<T> {
     size: Pt<usize>,
-    map: Vec<Vec<T>>,
+    cells: Vec<T>,
 }
 
 impl<T> Grid<T> {
     pub fn size(&self) -> (usize, usize) {
         (self.size.0, self.size.1)
     }
+
+    fn offset(&self, Pt(x, y): Pt<usize>) -> usize {
+        assert!(x < self.size.0, "column {} out of a grid of width {}", x, self.size.0);
+        y * self.size.0 + x
+    }
+
+    /// Decodes a grid of characters, `f` returning `None` for unexpected ones, and locates the `markers` such as a
+    /// start `S` or a guard `^`. Each marker must appear exactly once, and is decoded by `f` like any other cell.
+    pub fn parse<'a, const N: usize>(
+        lines: impl IntoIterator<Item = Span<'a>>,
+        markers: [char; N],
+        f: impl Fn(char) -> Option<T>,
+    ) -> Result<(Self, [Pt<usize>; N]), parse::Error> {
+        let mut cells = Vec::new();
+        let mut size = Pt(0, 0);
+        let mut found = [None; N];
+        let mut first = None;
+        for (y, line) in lines.into_iter().enumerate() {
+            first.get_or_insert(line);
+            cells.extend(line.chars(&f)?);
+            for (x, c) in line.as_str().chars().enumerate() {
+                if let Some(i) = markers.iter().position(|&m| m == c) {
+                    if found[i].replace(Pt(x, y)).is_some() {
+                        return Err(line.char_spans().nth(x).map_or(line, |(_, span)| span).error(format!("duplicate {:?}", c)));
+                    }
+                }
+            }
+            let width = cells.len() - size.0 * y;
+            if y == 0 {
+                size.0 = width;
+            } else if width != size.0 {
+                return Err(line.error(format!("expected {} cells as in the first row, found {}", size.0, width)));
+            }
+            size.1 += 1;
+        }
+        let first = first.unwrap_or(Span::new(1, ""));
+        let mut positions = [Pt(0, 0); N];
+        for ((pos, found), marker) in positions.iter_mut().zip(found).zip(markers) {
+            *pos = found.ok_or_else(|| first.error(format!("missing {:?} marker", marker)))?;
+        }
+        Ok((Self { size, cells }, positions))
+    }
 }
 
 impl<T: Copy> Grid<T> {
@@ -134,28 +281,25 @@ impl<T: Copy> Grid<T> {
     pub fn new_dyn(w: usize, h: usize, init: T) -> Self {
         Self {
             size: Pt(w, h),
-            map: vec![vec![init; w]; h],
+            cells: vec![init; w * h],
         }
     }
 }
 
-impl<T, II: IntoIterator<Item = T> + Clone> FromIterator<II> for Grid<T> {
+/// Builds a grid from its rows, which must all have the same length.
+impl<T, II: IntoIterator<Item = T>> FromIterator<II> for Grid<T> {
     fn from_iter<I: IntoIterator<Item = II>>(iter: I) -> Self {
-        let mut iter = iter.into_iter().peekable();
-        let peek: Option<&II> = iter.peek();
-        if let Some(peek) = peek {
-            let l = peek.clone().into_iter().count();
-            let res = iter.map(|r: II| r.into_iter().collect::<Vec<_>>()).collect::<Vec<_>>();
-            Self {
-                size: Pt(l, res.len()),
-                map: res,
-            }
-        } else {
-            Self {
-                size: Pt(0, 0),
-                map: Vec::new(),
+        let mut cells = Vec::new();
+        let mut size = Pt(0, 0);
+        for row in iter {
+            cells.extend(row);
+            if size.1 == 0 {
+                size.0 = cells.len();
             }
+            size.1 += 1;
+            assert_eq!(size.0 * size.1, cells.len(), "row {} of a grid of width {} has a different length", size.1 - 1, size.0);
         }
+        Self { size, cells }
     }
 }
 
@@ -168,7 +312,7 @@ fn into_range(
         Bound::Included(start) => start,
         Bound::Excluded(start) => start + 1,
         Bound::Unbounded => 0,
-    }.max(0);
+    };
 
     let end = match end {
         Bound::Included(end) => end + 1,
@@ -176,66 +320,230 @@ fn into_range(
         Bound::Unbounded => len,
     }.min(len);
 
-    // Don't bother with checking `start < end` and `end <= len`
-    // since these checks are handled by `Range` impls
+    // Empty rather than inverted when starting past the end, so the range can be used to slice.
+    start.min(end)..end
+}
+
+/// Rows and columns covered by a range of points, clamped to a grid of the given size.
+fn ranges(size: Pt<usize>, pts: impl std::ops::RangeBounds<Pt<usize>>) -> (Range<usize>, Range<usize>) {
+    let rows = ((pts.start_bound().cloned().map(|b| b.1)), (pts.end_bound().cloned().map(|b| b.1)));
+    let cols = ((pts.start_bound().cloned().map(|b| b.0)), (pts.end_bound().cloned().map(|b| b.0)));
+    (into_range(size.1, rows), into_range(size.0, cols))
+}
 
-    start..end
+/// Position next to `pt` in the direction `dir`, if it is still within a grid of the given size.
+fn shift(Pt(x, y): Pt<usize>, dir: Direction8, Pt(w, h): Pt<usize>) -> Option<Pt<usize>> {
+    let (dx, dy) = dir.delta();
+    let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+    (x < w && y < h).then_some(Pt(x, y))
 }
 
 impl<T> Grid<T> {
-    fn saturating_move(&self, pt: Pt<usize>, dir: Direction) -> Pt<usize> {
-        pt.saturating_add(dir, self.size)
+    /// Position next to `pos` in the direction `dir`, unless it is out of the grid.
+    pub fn step(&self, pos: Pt<usize>, dir: impl Into<Direction8>) -> Option<Pt<usize>> {
+        shift(pos, dir.into(), self.size)
     }
 
-    pub fn neigbours(&self, pos: Pt<usize>) -> impl Iterator<Item = &T> {
-        self.neighbour_positions(pos).map(|Pt(x, y)| &self.map[y][x])
+    pub fn neighbours(&self, pos: Pt<usize>) -> impl Iterator<Item = &T> {
+        self.neighbour_positions(pos).map(|Pt(x, y)| &self.cells[y * self.size.0 + x])
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.items(Pt(0, 0)..self.size)
+        self.cells.iter()
     }
     // pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
     //     self.items_mut(Pt(0, 0)..self.size)
     // }
     pub fn positions(&self, pts: impl std::ops::RangeBounds<Pt<usize>>) -> impl Iterator<Item = Pt<usize>> {
-        let rows = ((pts.start_bound().cloned().map(|b| b.1)), (pts.end_bound().cloned().map(|b| b.1)));
-        let cols = ((pts.start_bound().cloned().map(|b| b.0)), (pts.end_bound().cloned().map(|b| b.0)));
-        let rows = into_range(self.size.1, rows);
-        let cols = into_range(self.size.0, cols);
+        let (rows, cols) = ranges(self.size, pts);
         rows.flat_map(move |y| cols.clone().map(move |x| Pt(x, y)))
     }
     pub fn all_positions(&self) -> impl Iterator<Item = Pt<usize>> {
         self.positions(Pt(0, 0)..self.size)
     }
     pub fn neighbour_positions(&self, pos: Pt<usize>) -> impl Iterator<Item = Pt<usize>> {
-        let top = self.saturating_move(pos, Direction::N);
-        let bottom = self.saturating_move(pos, Direction::S);
-        self.positions(self.saturating_move(top, Direction::W)..=self.saturating_move(bottom, Direction::E))
-            .filter(move |&pt| pt != pos)
+        self.all_neighbours(pos).map(|(pt, _)| pt)
+    }
+    /// Positions north, east, south and west of `pos` which are within the grid, along with their direction.
+    pub fn orthogonal_neighbours(&self, pos: Pt<usize>) -> impl Iterator<Item = (Pt<usize>, Direction)> {
+        let size = self.size;
+        Direction::ALL.into_iter().filter_map(move |dir| Some((shift(pos, dir.into(), size)?, dir)))
+    }
+    /// Positions diagonally next to `pos` which are within the grid, along with their direction.
+    #[allow(dead_code)]
+    pub fn diagonal_neighbours(&self, pos: Pt<usize>) -> impl Iterator<Item = (Pt<usize>, Direction8)> {
+        let size = self.size;
+        Direction8::DIAGONALS.into_iter().filter_map(move |dir| Some((shift(pos, dir, size)?, dir)))
+    }
+    /// Positions of the 8 cells around `pos` which are within the grid, clockwise from north, along with their
+    /// direction.
+    pub fn all_neighbours(&self, pos: Pt<usize>) -> impl Iterator<Item = (Pt<usize>, Direction8)> {
+        let size = self.size;
+        Direction8::ALL.into_iter().filter_map(move |dir| Some((shift(pos, dir, size)?, dir)))
     }
     pub fn items(&self, pts: impl std::ops::RangeBounds<Pt<usize>>) -> impl Iterator<Item = &T> {
-        self.positions(pts).map(|Pt(x, y)| &self.map[y][x])
+        let (rows, cols) = ranges(self.size, pts);
+        rows.flat_map(move |y| self.row(y)[cols.clone()].iter())
     }
     pub fn items_mut(&mut self, pts: impl std::ops::RangeBounds<Pt<usize>>) -> impl Iterator<Item = &mut T> {
-        // self.positions(pts).map(|Pt(x, y)| self.map.get_mut(y).unwrap().get_mut(x).unwrap())
+        let (rows, cols) = ranges(self.size, pts);
+        let w = self.size.0;
+        // `chunks_exact_mut` rejects empty chunks, there is no row to split in an empty grid anyway.
+        self.cells[rows.start * w..rows.end * w].chunks_exact_mut(w.max(1))
+            .flat_map(move |row| row[cols.clone()].iter_mut())
+    }
 
-        let rows = ((pts.start_bound().cloned().map(|b| b.1)), (pts.end_bound().cloned().map(|b| b.1)));
-        let cols = ((pts.start_bound().cloned().map(|b| b.0)), (pts.end_bound().cloned().map(|b| b.0)));
-        self.map[rows].iter_mut()
-            .flat_map(move |row| row[cols].iter_mut())
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.size.0..(y + 1) * self.size.0]
+    }
+    #[allow(dead_code)]
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        let w = self.size.0;
+        &mut self.cells[y * w..(y + 1) * w]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.size.0.max(1))
+    }
+    /// Cells of column `x` from top to bottom, which are not contiguous unlike the ones of a row.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.size.0, "column {} out of a grid of width {}", x, self.size.0);
+        self.cells.iter().skip(x).step_by(self.size.0)
+    }
+    #[allow(dead_code)]
+    pub fn column_mut(&mut self, x: usize) -> impl Iterator<Item = &mut T> {
+        assert!(x < self.size.0, "column {} out of a grid of width {}", x, self.size.0);
+        let w = self.size.0;
+        self.cells.iter_mut().skip(x).step_by(w)
     }
 }
 
 impl<T> Index<Pt<usize>> for Grid<T> {
     type Output = T;
 
-    fn index(&self, Pt(x, y): Pt<usize>) -> &Self::Output {
-        &self.map[y][x]
+    fn index(&self, pt: Pt<usize>) -> &Self::Output {
+        &self.cells[self.offset(pt)]
     }
 }
 
 impl<T> IndexMut<Pt<usize>> for Grid<T> {
-    fn index_mut(&mut self, Pt(x, y): Pt<usize>) -> &mut Self::Output {
-        &mut self.map[y][x]
+    fn index_mut(&mut self, pt: Pt<usize>) -> &mut Self::Output {
+        let offset = self.offset(pt);
+        &mut self.cells[offset]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        [[1, 2, 3], [4, 5, 6]].into_iter().collect()
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut grid = grid();
+        assert_eq!((3, 2), grid.size());
+        assert_eq!(6, grid[Pt(2, 1)]);
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&[1, 2, 3][..], &[4, 5, 6]], grid.rows().collect::<Vec<_>>());
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        grid.column_mut(0).for_each(|v| *v *= 10);
+        grid.row_mut(0)[2] = 0;
+        assert_eq!(vec![10, 2, 0, 40, 5, 6], grid.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn items() {
+        let mut grid = grid();
+        assert_eq!(vec![2, 3, 5, 6], grid.items(Pt(1, 0)..=Pt(2, 1)).copied().collect::<Vec<_>>());
+        assert_eq!(vec![Pt(0, 1), Pt(1, 1)], grid.positions(Pt(0, 1)..Pt(2, 2)).collect::<Vec<_>>());
+        grid.items_mut(Pt(0, 1)..).for_each(|v| *v = 0);
+        assert_eq!(vec![1, 2, 3, 0, 0, 0], grid.iter().copied().collect::<Vec<_>>());
+        // Out of the grid or inverted ranges are empty.
+        assert_eq!(0, grid.items(Pt(3, 0)..Pt(5, 2)).count());
+        assert_eq!(0, grid.items(Pt(2, 1)..Pt(1, 0)).count());
+        assert_eq!(0, grid.positions(Pt(4, 0)..).count());
+        assert_eq!(0, grid.items_mut(Pt(0, 5)..).count());
+        assert_eq!(vec![3, 0], grid.items(Pt(2, 0)..Pt(7, 9)).copied().collect::<Vec<_>>());
+        assert_eq!(vec![2, 3, 0, 0, 1], grid.neighbours(Pt(1, 1)).copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(vec![Direction::W, Direction::N, Direction::E, Direction::S], Direction::W.clockwise().collect::<Vec<_>>());
+        assert_eq!(Direction::ALL.to_vec(), Direction::N.clockwise().collect::<Vec<_>>());
+        assert_eq!([Direction::S, Direction::W, Direction::N, Direction::E], Direction::ALL.map(Direction::reverse));
+        assert!(Direction::ALL.iter().all(|&d| d.reverse().reverse() == d && d + Side::Right + Side::Right == d.reverse()));
+        assert!(Direction::ALL.iter().all(|&d| d + Side::Left == d.clockwise().last().unwrap()));
+        for (dir, notations) in Direction::ALL.iter().zip(["^UN", ">RE", "vDS", "<LW"]) {
+            assert!(notations.chars().map(Direction::try_from).all(|d| d.is_ok_and(|d| d == *dir)));
+        }
+        assert!(Direction::try_from('x').is_err());
+        assert_eq!((0, -1), Direction::N.delta());
+        assert_eq!(Pt(2, 3), Pt(2, 2) + Direction::S);
+        assert_eq!((-1, 0), Direction::W.delta());
+    }
+
+    #[test]
+    fn moves() {
+        let grid = grid();
+        assert_eq!(Some(Pt(2, 1)), grid.step(Pt(1, 1), Direction::E));
+        assert_eq!(None, grid.step(Pt(2, 1), Direction::E));
+        assert_eq!(None, grid.step(Pt(0, 1), Direction8::SW));
+        assert_eq!(Some(Pt(0, 0)), grid.step(Pt(1, 1), Direction8::NW));
+        assert_eq!(Pt(5, 5), Pt(10_i64, 3).torus_add(Pt(2_i64, -4) * 3, Pt(11, 7)));
+        // Robot of the example of 2024 day 14 after 5 seconds, with a negative velocity.
+        assert_eq!(Pt(1, 3), Pt(2_i64, 4).torus_add(Pt(2_i64, -3) * 5, Pt(11, 7)));
+        assert_eq!(Pt(10, 6), Pt(0_i64, 0).torus_add(Pt(-12, -8), Pt(11, 7)));
+        assert_eq!(None, Pt(0_usize, 3).checked_add(Direction::W));
+        assert_eq!(None, Pt(3_usize, 0).checked_add(Direction::N));
+        assert_eq!(Some(Pt(3, 1)), Pt(3_usize, 0).checked_add(Direction::S));
+        assert_eq!(None, Pt(usize::MAX, 0).checked_add(Direction::E));
+        assert_eq!(Pt(0, 2), Pt(0_usize, 2).saturating_add(Direction::W, Pt(3, 3)));
+        assert_eq!(Pt(2, 2), Pt(2_usize, 2).saturating_add(Direction::E, Pt(3, 3)));
+        assert_eq!(Pt(1, 2), Pt(2_usize, 2).saturating_add(Direction::W, Pt(3, 3)));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        assert_eq!(
+            vec![(Pt(1, 0), Direction::N), (Pt(2, 1), Direction::E), (Pt(0, 1), Direction::W)],
+            grid.orthogonal_neighbours(Pt(1, 1)).collect::<Vec<_>>(),
+        );
+        assert_eq!(vec![(Pt(1, 1), Direction8::SE)], grid.diagonal_neighbours(Pt(0, 0)).collect::<Vec<_>>());
+        assert_eq!(
+            vec![Direction8::N, Direction8::W, Direction8::NW],
+            grid.all_neighbours(Pt(2, 1)).map(|(_, d)| d).collect::<Vec<_>>(),
+        );
+        assert!(Direction8::ALL.iter().all(|&d| Direction::try_from(d).map(Direction8::from).map_or(true, |o| o == d)));
+        assert_eq!(4, Direction8::ALL.iter().filter(|&&d| Direction::try_from(d).is_err()).count());
+    }
+
+    #[test]
+    fn parse() {
+        let decode = |c| match c {
+            '#' => Some(true),
+            '.' | 'S' | 'E' => Some(false),
+            _ => None,
+        };
+        let (grid, []) = Grid::parse(parse::lines("#.\n.#"), [], decode).unwrap();
+        assert_eq!((2, 2), grid.size());
+        assert!(grid[Pt(1, 1)]);
+        let (_, [start, end]) = Grid::parse(parse::lines("#E.\n.S#"), ['S', 'E'], decode).unwrap();
+        assert_eq!((Pt(1, 1), Pt(1, 0)), (start, end));
+
+        let err = |input| Grid::parse(parse::lines(input), ['S'], decode).unwrap_err().to_string();
+        assert_eq!("line 2, column 2: unexpected '?' in \"?\"", err("#S\n.?"));
+        assert_eq!("line 2, column 1: expected 2 cells as in the first row, found 3 in \"..#\"", err("#S\n..#"));
+        assert_eq!("line 2, column 2: duplicate 'S' in \"S\"", err("#S\n.S"));
+        assert_eq!("line 1, column 1: missing 'S' marker in \"#.\"", err("#.\n.#"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn ragged() {
+        let _ = [vec![1, 2], vec![3]].into_iter().collect::<Grid<u8>>();
     }
 }
@@ -0,0 +1,167 @@
+use std::{fmt::Display, io::{BufRead, Lines}, str::FromStr};
+
+use thiserror::Error;
+
+/// Malformed input, located by its line and column (both starting at 1) along with the offending text.
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("line {line}, column {column}: {message} in {text:?}")]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+/// Part of a line of the input which remembers where it comes from, so errors can point at it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(line: usize, text: &'a str) -> Self {
+        Self { line, column: 1, text }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn error(&self, message: impl Display) -> Error {
+        Error { line: self.line, column: self.column, text: self.text.to_string(), message: message.to_string() }
+    }
+
+    /// Sub span starting `offset` bytes after the start of this one.
+    fn sub(&self, offset: usize, len: usize) -> Self {
+        Self { line: self.line, column: self.column + self.text[..offset].chars().count(), text: &self.text[offset..offset+len] }
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Self, Error> {
+        if self.text.starts_with(prefix) {
+            Ok(self.sub(prefix.len(), self.text.len() - prefix.len()))
+        } else {
+            Err(self.error(format!("expected {:?}", prefix)))
+        }
+    }
+
+    pub fn split_once(&self, delim: &str) -> Result<(Self, Self), Error> {
+        let pos = self.text.find(delim).ok_or_else(|| self.error(format!("missing {:?}", delim)))?;
+        Ok((self.sub(0, pos), self.sub(pos + delim.len(), self.text.len() - pos - delim.len())))
+    }
+
+    pub fn split(&self, delim: &'a str) -> impl Iterator<Item = Self> + 'a {
+        let span = *self;
+        self.text.split(delim).map(move |part| span.sub(part.as_ptr() as usize - span.text.as_ptr() as usize, part.len()))
+    }
+
+    /// Splits on `delim` into exactly `N` fields.
+    pub fn fields<const N: usize>(&self, delim: &'a str) -> Result<[Self; N], Error> {
+        let fields = self.split(delim).collect::<Vec<_>>();
+        let count = fields.len();
+        fields.try_into().map_err(|_| self.error(format!("expected {} fields separated by {:?}, found {}", N, delim, count)))
+    }
+
+    pub fn parse<T: FromStr>(&self) -> Result<T, Error> where T::Err: Display {
+        self.text.parse().map_err(|e| self.error(e))
+    }
+
+    /// Parses every field separated by `delim`, e.g. `1,2,3`.
+    pub fn list<T: FromStr>(&self, delim: &'a str) -> Result<Vec<T>, Error> where T::Err: Display {
+        self.split(delim).map(|s| s.parse()).collect()
+    }
+
+    /// Parses every whitespace separated field.
+    pub fn words<T: FromStr>(&self) -> Result<Vec<T>, Error> where T::Err: Display {
+        self.text.split_whitespace().map(|w| self.sub(w.as_ptr() as usize - self.text.as_ptr() as usize, w.len()).parse()).collect()
+    }
+
+    /// Each character along with its own span.
+    pub fn char_spans(&self) -> impl Iterator<Item = (char, Self)> + 'a {
+        let span = *self;
+        self.text.char_indices().map(move |(i, c)| (c, span.sub(i, c.len_utf8())))
+    }
+
+    /// Decodes each character, `f` returning `None` for unexpected ones.
+    pub fn chars<T>(&self, f: impl Fn(char) -> Option<T>) -> Result<Vec<T>, Error> {
+        // Spans are only built for the errors, computing their column being costly on such a hot path.
+        self.text.char_indices().map(|(i, c)| f(c).ok_or_else(|| self.sub(i, c.len_utf8()).error(format!("unexpected {:?}", c)))).collect()
+    }
+}
+
+/// Lines of an input kept in memory, the spans borrowing from it.
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    input.lines().enumerate().map(|(i, l)| Span::new(i + 1, l))
+}
+
+/// Groups the lines of an input kept in memory separated by blank ones.
+pub fn sections(input: &str) -> Vec<Vec<Span<'_>>> {
+    group(lines(input))
+}
+
+fn group<'a>(lines: impl Iterator<Item = Span<'a>>) -> Vec<Vec<Span<'a>>> {
+    lines.collect::<Vec<_>>().split(|l| l.is_empty()).filter(|s| !s.is_empty()).map(|s| s.to_vec()).collect()
+}
+
+/// Whole input read line by line, so lines can be borrowed by the spans pointing at them.
+pub struct Text(Vec<String>);
+
+impl Text {
+    pub fn read<B: BufRead>(lines: Lines<B>) -> anyhow::Result<Self> {
+        Ok(Self(lines.collect::<Result<Vec<_>, _>>()?))
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'_>> {
+        self.0.iter().enumerate().map(|(i, l)| Span::new(i + 1, l))
+    }
+
+    /// Groups the lines separated by blank ones.
+    pub fn sections(&self) -> Vec<Vec<Span<'_>>> {
+        group(self.lines())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> Text {
+        Text(s.lines().map(String::from).collect())
+    }
+
+    #[test]
+    fn fields() -> Result<(), Error> {
+        let text = text("p=0,4 v=3,-3\np=6,3 v=-1;-3");
+        let [pos, speed] = text.lines().nth(1).unwrap().fields(" ")?;
+        assert_eq!(vec![6, 3], pos.strip_prefix("p=")?.list::<i64>(",")?);
+        let err = speed.strip_prefix("v=")?.split_once(",").unwrap_err();
+        assert_eq!((2, 9, "-1;-3".to_string()), (err.line, err.column, err.text));
+        let err = text.lines().next().unwrap().fields::<3>(" ").unwrap_err();
+        assert_eq!("line 1, column 1: expected 3 fields separated by \" \", found 2 in \"p=0,4 v=3,-3\"", err.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn values() {
+        let line = Span::new(3, "12, 7,x");
+        let err = line.list::<u64>(",").unwrap_err();
+        assert_eq!((3, 4, " 7".to_string()), (err.line, err.column, err.text));
+        let err = line.split(",").skip(2).map(|s| s.parse::<u64>()).collect::<Result<Vec<_>, _>>().unwrap_err();
+        assert_eq!((3, 7, "x".to_string()), (err.line, err.column, err.text));
+        assert_eq!(Ok(vec![1, 2, 3]), Span::new(1, " 1  2 3").words::<u8>());
+    }
+
+    #[test]
+    fn sections() {
+        let text = text("a\nb\n\nc\n\nd\ne");
+        let sections = text.sections().iter().map(|s| s.iter().map(|l| l.as_str()).collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert_eq!(vec![vec!["a", "b"], vec!["c"], vec!["d", "e"]], sections);
+        assert_eq!(4, text.sections()[1][0].line);
+        assert_eq!(sections, super::sections("a\nb\n\nc\n\nd\ne").iter().map(|s| s.iter().map(|l| l.as_str()).collect::<Vec<_>>()).collect::<Vec<_>>());
+    }
+}
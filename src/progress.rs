@@ -0,0 +1,137 @@
+use std::fmt::Display;
+
+use crate::{answers::{self, Verdict}, registry::{self, Day, DAYS_PER_YEAR}, runner, solver::{Implementation, Part}};
+
+const SIZE: usize = 30;
+const CODE: [char; 7] = ['\u{28C0}', '\u{28C4}', '\u{28C6}',  '\u{28C7}', '\u{28E7}', '\u{28F7}', '\u{28FF}'];
+
+fn bar(progress: usize) -> String {
+    let p = progress*SIZE*CODE.len()/100;
+    (0..SIZE).map(|l| p.saturating_sub(l*CODE.len()).min(CODE.len() - 1)).map(|l| CODE[l]).collect::<String>()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// No solver is registered for the day.
+    Missing,
+    /// The solver reports that the part is not implemented.
+    Unimplemented,
+    /// The answer was found by hand.
+    Hardcoded,
+    Implemented,
+    /// The answer matches the stored one.
+    Verified,
+    /// The answer differs from the stored one or could not be computed.
+    Failing,
+}
+
+impl Status {
+    /// Whether the part earns its star.
+    pub fn star(self) -> bool {
+        matches!(self, Self::Implemented | Self::Verified)
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Self::Missing => '.',
+            Self::Unimplemented => '?',
+            Self::Hardcoded => '~',
+            Self::Implemented => '+',
+            Self::Verified => '*',
+            Self::Failing => '!',
+        }
+    }
+}
+
+/// Status of both parts of a day, from their declared implementation only.
+pub fn declared(day: Option<&Day>) -> [Status; 2] {
+    Part::ALL.map(|part| match day.map(|d| d.implementation[part.index()]) {
+        None => Status::Missing,
+        Some(Implementation::Missing) => Status::Unimplemented,
+        Some(Implementation::Hardcoded) => Status::Hardcoded,
+        Some(Implementation::Complete) => Status::Implemented,
+    })
+}
+
+/// Status of both parts of a day, implemented parts being run and checked against their stored answers.
+pub fn verified(day: Option<&'static Day>, file: impl FnOnce(&Day) -> String) -> [Status; 2] {
+    let mut status = declared(day);
+    let Some(day) = day else {
+        return status;
+    };
+    let Ok(answers) = answers::load(day.year, day.day) else {
+        return status;
+    };
+    let parts = Part::ALL.into_iter().zip(status).filter(|(_, s)| *s == Status::Implemented).map(|(p, _)| p).collect::<Vec<_>>();
+    runner::run_day(day, &file(day), &[], &parts, None).into_iter().for_each(|record| {
+        status[record.part.index()] = match record.answer.map(|answer| Verdict::check(answers.get(record.part), &answer)) {
+            Ok(Verdict::Pass) => Status::Verified,
+            Ok(Verdict::Missing) => Status::Implemented,
+            Ok(Verdict::Fail { .. }) | Err(_) => Status::Failing,
+        };
+    });
+    status
+}
+
+pub struct Year {
+    pub year: u16,
+    pub days: Vec<[Status; 2]>,
+}
+
+impl Year {
+    pub fn new(year: u16, status: impl FnMut(Option<&'static Day>) -> [Status; 2]) -> Self {
+        Self { year, days: (1..=DAYS_PER_YEAR).map(|d| registry::find(year, d)).map(status).collect() }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.days.iter().flatten().filter(|s| s.star()).count()
+    }
+
+    /// Two percents per star, as each year holds fifty of them.
+    pub fn percent(&self) -> usize {
+        self.stars()*100/(2*DAYS_PER_YEAR as usize)
+    }
+}
+
+impl Display for Year {
+    /// Renders the calendar of the year, one symbol per part of each day.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let days = self.days.iter().map(|[one, two]| format!("{}{}", one.symbol(), two.symbol())).collect::<Vec<_>>();
+        write!(f, "{}   {}", self.year, days.join(" "))
+    }
+}
+
+pub fn print(years: &[Year]) {
+    years.iter().for_each(|year| {
+        println!("{}   {} {:3}% {:>2}*", year.year, bar(year.percent()), year.percent(), year.stars());
+    });
+    let global = years.iter().map(Year::percent).sum::<usize>()/years.len().max(1);
+    println!("Global {} {:3}%", bar(global), global);
+    println!();
+    println!("       {}", (1..=DAYS_PER_YEAR).map(|d| format!("{:02}", d)).collect::<Vec<_>>().join(" "));
+    years.iter().for_each(|year| println!("{}", year));
+    println!();
+    println!("* verified  + implemented  ~ hardcoded  ! failing  ? not implemented  . missing");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders() {
+        let year = Year::new(2024, declared);
+        assert_eq!([Status::Implemented, Status::Unimplemented], year.days[24]);
+        assert_eq!([Status::Implemented, Status::Hardcoded], year.days[23]);
+        assert_eq!(48, year.stars());
+        assert_eq!(96, year.percent());
+    }
+
+    #[test]
+    fn missing_days() {
+        let year = Year::new(2017, declared);
+        assert_eq!([Status::Missing, Status::Missing], year.days[2]);
+        assert_eq!(4, year.stars());
+        assert_eq!("2017   ++ ++ ..", &year.to_string()[..15]);
+    }
+}
@@ -0,0 +1,59 @@
+use std::ops::RangeInclusive;
+
+use crate::solver::{self, DynSolver, Implementation, Solver};
+
+pub const YEARS: RangeInclusive<u16> = 2015..=2024;
+pub const DAYS_PER_YEAR: u8 = 25;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    /// Builds the problem from the whole input, which it may borrow from.
+    pub parse: for<'a> fn(&'a str) -> anyhow::Result<Box<dyn DynSolver + 'a>>,
+    pub implementation: [Implementation; 2],
+}
+
+macro_rules! registry {
+    ($($year:literal => $module:ident [$($day:literal)*],)*) => {
+        #[allow(clippy::zero_prefixed_literal)]
+        pub static DAYS: &[Day] = &[$($(
+            paste::paste! {
+                Day {
+                    year: $year,
+                    day: $day,
+                    parse: |input| solver::parse::<crate::$module::[<d $day>]::Problem>(input),
+                    implementation: <crate::$module::[<d $day>]::Problem as Solver>::IMPLEMENTATION,
+                }
+            },
+        )*)*];
+    };
+}
+
+// Every day found by the build script, so that adding its file is enough to register it.
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+pub fn missing_days(year: u16) -> impl Iterator<Item = u8> {
+    (1..=DAYS_PER_YEAR).filter(move |&day| find(year, day).is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn unique_entries() {
+        let entries = DAYS.iter().map(|d| (d.year, d.day)).collect::<HashSet<_>>();
+        assert_eq!(DAYS.len(), entries.len());
+    }
+
+    #[test]
+    fn entries_within_calendar() {
+        assert!(DAYS.iter().all(|d| YEARS.contains(&d.year) && (1..=DAYS_PER_YEAR).contains(&d.day)));
+    }
+}
@@ -0,0 +1,159 @@
+use std::borrow::Cow;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{allocs, runner::Record, solver::{Implementation, Part}};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    #[value(help = "human readable output")]
+    Table,
+    #[value(help = "an array of records")]
+    Json,
+    #[value(help = "one record per line after a header")]
+    Csv,
+}
+
+/// Structured counterpart of a [`Record`], times being given in nanoseconds. Allocations are left out of the CSV
+/// format, which keeps a fixed set of columns.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub implementation: Implementation,
+    pub answer: Option<String>,
+    /// Known answer, only looked up when verifying.
+    pub expected: Option<String>,
+    pub parse_ns: u64,
+    pub prepare_ns: u64,
+    pub solve_ns: u64,
+    pub input: String,
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocs: Option<allocs::Phases>,
+}
+
+const COLUMNS: &str = "year,day,part,implementation,answer,expected,parse_ns,prepare_ns,solve_ns,input,error";
+
+impl Row {
+    pub fn new(record: &Record, expected: Option<String>) -> Self {
+        Self {
+            year: record.year,
+            day: record.day,
+            part: record.part,
+            implementation: record.implementation,
+            answer: record.answer.as_ref().ok().cloned(),
+            expected,
+            parse_ns: record.parse.as_nanos() as u64,
+            prepare_ns: record.prepare.as_nanos() as u64,
+            solve_ns: record.solve.as_nanos() as u64,
+            input: record.input.clone(),
+            error: record.answer.as_ref().err().map(|e| format!("{:#}", e)),
+            allocs: record.allocs,
+        }
+    }
+
+    fn csv(&self) -> String {
+        let fields = [
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.implementation.to_string(),
+            self.answer.clone().unwrap_or_default(),
+            self.expected.clone().unwrap_or_default(),
+            self.parse_ns.to_string(),
+            self.prepare_ns.to_string(),
+            self.solve_ns.to_string(),
+            self.input.clone(),
+            self.error.clone().unwrap_or_default(),
+        ];
+        fields.iter().map(|f| escape(f)).collect::<Vec<_>>().join(",")
+    }
+}
+
+/// Quotes a CSV field when it holds a separator, a quote or a line break.
+fn escape(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Prints the rows in a structured format, the table one being printed by the caller as it goes.
+pub fn emit(format: Format, rows: &[Row]) -> anyhow::Result<()> {
+    match format {
+        Format::Table => {},
+        Format::Json => println!("{}", serde_json::to_string_pretty(rows)?),
+        Format::Csv => {
+            println!("{}", COLUMNS);
+            rows.iter().for_each(|row| println!("{}", row.csv()));
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use anyhow::anyhow;
+
+    use super::*;
+
+    fn record(answer: anyhow::Result<String>) -> Record {
+        Record {
+            year: 2024,
+            day: 23,
+            part: Part::Two,
+            input: "examples/inputs/24/23".to_string(),
+            implementation: Implementation::Complete,
+            answer,
+            parse: Duration::from_micros(3),
+            prepare: Duration::from_nanos(7),
+            solve: Duration::from_nanos(42),
+            allocs: None,
+            still_running: false,
+        }
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            "2024,23,two,complete,\"co,de,ka,ta\",,3000,7,42,examples/inputs/24/23,",
+            Row::new(&record(Ok("co,de,ka,ta".to_string())), None).csv(),
+        );
+        assert_eq!(
+            "2024,23,two,complete,,x,3000,7,42,examples/inputs/24/23,\"unable to parse input: \"\"a\"\"\"",
+            Row::new(&record(Err(anyhow!("unable to parse input: \"a\""))), Some("x".to_string())).csv(),
+        );
+        assert_eq!(COLUMNS.split(',').count(), Row::new(&record(Ok("1".to_string())), None).csv().split(',').count());
+    }
+
+    #[test]
+    fn json() -> anyhow::Result<()> {
+        let row = serde_json::to_value(Row::new(&record(Err(anyhow!("boom"))), None))?;
+        assert_eq!(serde_json::json!({
+            "year": 2024,
+            "day": 23,
+            "part": "two",
+            "implementation": "complete",
+            "answer": null,
+            "expected": null,
+            "parse_ns": 3000,
+            "prepare_ns": 7,
+            "solve_ns": 42,
+            "input": "examples/inputs/24/23",
+            "error": "boom",
+        }), row);
+
+        let phases = allocs::Phases { solve: allocs::Stats { allocations: 2, bytes: 48, peak_bytes: 32 }, ..Default::default() };
+        let row = serde_json::to_value(Row::new(&Record { allocs: Some(phases), ..record(Ok("1".to_string())) }, None))?;
+        assert_eq!(serde_json::json!({ "allocations": 2, "bytes": 48, "peak_bytes": 32 }), row["allocs"]["solve"]);
+        assert_eq!(serde_json::json!({ "allocations": 0, "bytes": 0, "peak_bytes": 0 }), row["allocs"]["parse"]);
+        Ok(())
+    }
+}
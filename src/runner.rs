@@ -0,0 +1,345 @@
+use std::{any::Any, fs, io::{self, Read}, panic::{self, AssertUnwindSafe}, sync::mpsc::{self, RecvTimeoutError}, thread, time::{Duration, Instant}};
+
+use anyhow::{anyhow, Context};
+use thiserror::Error;
+
+use crate::{allocs, answers::{self, Verdict}, cancel, registry::Day, report::{self, Format, Row}, solver::{self, Implementation, Part}};
+
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub implementation: Implementation,
+    pub answer: anyhow::Result<String>,
+    pub parse: Duration,
+    /// Time spent building the state shared by the parts, only once per run.
+    pub prepare: Duration,
+    pub solve: Duration,
+    /// Only counted when enabled, see [`allocs::enable`].
+    pub allocs: Option<allocs::Phases>,
+    /// Whether the part timed out and its solver kept running in the background, not checking its cancellation
+    /// token. It then competes for the CPU with the following runs.
+    pub still_running: bool,
+}
+
+impl Record {
+    /// Record of a part which did not get to be solved, durations being left empty.
+    fn new(day: &Day, file: &str, part: Part, answer: anyhow::Result<String>) -> Self {
+        Self {
+            year: day.year,
+            day: day.day,
+            part,
+            input: file.to_string(),
+            implementation: day.implementation[part.index()],
+            answer,
+            parse: Duration::ZERO,
+            prepare: Duration::ZERO,
+            solve: Duration::ZERO,
+            allocs: None,
+            still_running: false,
+        }
+    }
+
+    fn failed(&self, kind: solver::Error) -> bool {
+        self.answer.as_ref().is_err_and(|e| e.root_cause().downcast_ref() == Some(&kind))
+    }
+
+    /// Whether the solver reported that the part is not implemented, which is not considered as a failure.
+    pub fn unimplemented(&self) -> bool {
+        self.failed(solver::Error::NotImplemented)
+    }
+
+    pub fn timed_out(&self) -> bool {
+        self.failed(solver::Error::Timeout)
+    }
+}
+
+/// Reads a whole input file in memory, `-` standing for stdin.
+pub fn read(file: &str) -> anyhow::Result<String> {
+    if file == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).context("unable to read stdin")?;
+        Ok(input)
+    } else {
+        fs::read_to_string(file).with_context(|| format!("unable to read {}", file))
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Panic of a solver, caught by [`catch`].
+#[derive(Error, Debug, Clone)]
+#[error("panicked: {0}")]
+pub struct Panic(pub String);
+
+/// Runs `f`, turning a panic into a regular error.
+pub fn catch<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(Panic(panic_message(payload)).into()))
+}
+
+/// Runs `f` without the default panic output, panics being reported through [`catch`] instead.
+pub fn quiet_panics<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let res = f();
+    panic::set_hook(hook);
+    res
+}
+
+/// Copies an error so it can be reported on several parts, keeping the kind of failure which caused it.
+fn duplicate(e: &anyhow::Error) -> anyhow::Error {
+    let root = e.root_cause();
+    let copy = if let Some(kind) = root.downcast_ref::<solver::Error>() {
+        anyhow::Error::new(kind.clone())
+    } else if let Some(panic) = root.downcast_ref::<Panic>() {
+        anyhow::Error::new(panic.clone())
+    } else {
+        anyhow!("{}", root)
+    };
+    e.chain().rev().skip(1).fold(copy, |copy, cause| copy.context(cause.to_string()))
+}
+
+/// Runs `f`, measuring its duration along with its allocations.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Phase) {
+    let start = Instant::now();
+    let (res, allocs) = allocs::measure(f);
+    (res, Phase { time: start.elapsed(), allocs })
+}
+
+#[derive(Clone, Copy, Default)]
+struct Phase {
+    time: Duration,
+    allocs: allocs::Stats,
+}
+
+/// Time given to a cancelled worker to notice it and stop.
+const GRACE: Duration = Duration::from_millis(100);
+
+/// Reads and parses the input file once, overrides the given parameters, prepares the shared state then solves
+/// every requested part, a failure being reported on each of them.
+///
+/// With a `timeout`, this happens on a worker thread and each part, the first one including parsing and preparation,
+/// must be solved in time. Otherwise it is reported as [`solver::Error::Timeout`] along with the following ones and the
+/// worker is asked to stop through its [`cancel::Token`]. If it does not check it, the worker is left behind and the
+/// record of the part tells it is still running.
+pub fn run_day(day: &'static Day, file: &str, params: &[(String, String)], parts: &[Part], timeout: Option<Duration>) -> Vec<Record> {
+    let input = read(file);
+    let Some(timeout) = timeout else {
+        let mut records = Vec::new();
+        solve_day(day, input, file, params, parts, |record| records.push(record));
+        return records;
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let token = cancel::Token::default();
+    let worker = token.clone();
+    let (owned_file, owned_params, owned_parts) = (file.to_string(), params.to_vec(), parts.to_vec());
+    let handle = thread::spawn(move || {
+        cancel::install(worker);
+        solve_day(day, input, &owned_file, &owned_params, &owned_parts, |record| {
+            // The runner stops listening once a part timed out.
+            let _ = tx.send(record);
+        });
+    });
+    let mut records = Vec::new();
+    while records.len() < parts.len() {
+        let (error, solve) = match rx.recv_timeout(timeout) {
+            Ok(record) => {
+                records.push(record);
+                continue;
+            },
+            Err(RecvTimeoutError::Timeout) => (solver::Error::Timeout.into(), timeout),
+            Err(RecvTimeoutError::Disconnected) => (anyhow!("worker thread stopped unexpectedly"), Duration::ZERO),
+        };
+        token.cancel();
+        let start = Instant::now();
+        while !handle.is_finished() && start.elapsed() < GRACE {
+            thread::sleep(Duration::from_millis(1));
+        }
+        let still_running = !handle.is_finished();
+        let error = if still_running { error.context("left running in the background") } else { error };
+        records.push(Record { solve, still_running, ..Record::new(day, file, parts[records.len()], Err(duplicate(&error))) });
+        records.extend(parts[records.len()..].iter().map(|&part| Record::new(day, file, part, Err(duplicate(&error)))));
+    }
+    records
+}
+
+/// Solves the parts of a day from its input, passing each record to `emit` as soon as it is known.
+fn solve_day(day: &Day, input: anyhow::Result<String>, file: &str, params: &[(String, String)], parts: &[Part], mut emit: impl FnMut(Record)) {
+    let record = |part: Part, answer, parse: Phase, prepare: Phase, solve: Phase| Record {
+        parse: parse.time,
+        prepare: prepare.time,
+        solve: solve.time,
+        allocs: allocs::enabled().then_some(allocs::Phases { parse: parse.allocs, prepare: prepare.allocs, solve: solve.allocs }),
+        ..Record::new(day, file, part, answer)
+    };
+    let (problem, parse) = measure(|| input.as_deref()
+        .map_err(duplicate)
+        .and_then(|input| catch(|| (day.parse)(input)).context("unable to parse input"))
+        .and_then(|mut problem| {
+            params.iter().try_for_each(|(name, value)| problem.set(name, value))?;
+            Ok(problem)
+        }));
+    let problem = match problem {
+        Ok(problem) => problem,
+        Err(e) => {
+            return parts.iter().for_each(|&part| emit(record(part, Err(duplicate(&e)), parse, Phase::default(), Phase::default())));
+        },
+    };
+    let (prepared, prepare) = measure(|| catch(|| {
+        problem.prepare();
+        Ok(())
+    }));
+    if let Err(e) = prepared {
+        return parts.iter().for_each(|&part| emit(record(part, Err(duplicate(&e)), parse, prepare, Phase::default())));
+    }
+    parts.iter().for_each(|&part| {
+        let (answer, solve) = measure(|| catch(|| Ok(problem.solve(part)?)));
+        emit(record(part, answer, parse, prepare, solve));
+    });
+}
+
+/// Warns about the solvers left running after timing out, which slowed down the runs following them.
+fn warn_running(count: usize) {
+    if count > 0 {
+        eprintln!("warning: {} timed out solvers did not stop and kept running, the timings of the following days may be distorted", count);
+    }
+}
+
+/// Runs both parts of each day, printing a results table as it goes or every record at the end in a structured
+/// format. The table only shows the allocations of solving each part, the structured formats giving every phase.
+pub fn batch(days: impl Iterator<Item = &'static Day>, file: impl Fn(&Day) -> String, format: Format, timeout: Option<Duration>) -> anyhow::Result<()> {
+    if format == Format::Table {
+        print!("{:<4} {:>3} {:<4} {:<40} {:>12}", "year", "day", "part", "answer", "time");
+        if allocs::enabled() {
+            print!(" {:>11} {:>14} {:>14}", "allocations", "bytes", "peak bytes");
+        }
+        println!();
+    }
+    let (mut total, mut failed, mut running) = (0, 0, 0);
+    let mut rows = Vec::new();
+    days.for_each(|day| {
+        run_day(day, &file(day), &[], &Part::ALL, timeout).into_iter().for_each(|record| {
+            total += 1;
+            running += record.still_running as usize;
+            failed += (record.answer.is_err() && !record.unimplemented()) as usize;
+            if format == Format::Table {
+                let answer = match &record.answer {
+                    _ if record.unimplemented() => "NOT IMPLEMENTED".to_string(),
+                    _ if record.still_running => "TIMEOUT (still running)".to_string(),
+                    _ if record.timed_out() => "TIMEOUT".to_string(),
+                    Ok(answer) if record.implementation == Implementation::Hardcoded => format!("{} (hardcoded)", answer),
+                    Ok(answer) => answer.clone(),
+                    Err(e) => format!("ERROR {:#}", e),
+                };
+                print!("{:<4} {:>3} {:<4} {:<40} {:>12}", record.year, record.day, record.part, answer, format!("{:.2?}", record.solve));
+                if let Some(allocs) = record.allocs {
+                    print!(" {:>11} {:>14} {:>14}", allocs.solve.allocations, allocs.solve.bytes, allocs.solve.peak_bytes);
+                }
+                println!();
+            } else {
+                rows.push(Row::new(&record, None));
+            }
+        });
+    });
+    report::emit(format, &rows)?;
+    warn_running(running);
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {total} runs failed");
+    }
+    Ok(())
+}
+
+/// Checks both parts of each day against their known answers.
+pub fn verify(mut days: impl Iterator<Item = &'static Day>, file: impl Fn(&Day) -> String, format: Format, timeout: Option<Duration>) -> anyhow::Result<()> {
+    let (mut total, mut failed, mut running) = (0, 0, 0);
+    let mut rows = Vec::new();
+    days.try_for_each(|day| {
+        let answers = answers::load(day.year, day.day)?;
+        run_day(day, &file(day), &[], &Part::ALL, timeout).into_iter().for_each(|record| {
+            total += 1;
+            running += record.still_running as usize;
+            let status = match &record.answer {
+                _ if record.unimplemented() => "SKIP (not implemented)".to_string(),
+                Ok(answer) => match Verdict::check(answers.get(record.part), answer) {
+                    Verdict::Pass if record.implementation == Implementation::Hardcoded => "PASS (hardcoded)".to_string(),
+                    Verdict::Pass => "PASS".to_string(),
+                    Verdict::Missing => format!("MISSING (got {})", answer),
+                    Verdict::Fail { expected } => {
+                        failed += 1;
+                        format!("FAIL (got {}, expected {})", answer, expected)
+                    },
+                },
+                Err(_) if record.timed_out() => {
+                    failed += 1;
+                    if record.still_running { "TIMEOUT (still running)" } else { "TIMEOUT" }.to_string()
+                },
+                Err(e) => {
+                    failed += 1;
+                    format!("FAIL ({:#})", e)
+                },
+            };
+            if format == Format::Table {
+                println!("{:<4} {:>3} {:<4} {}", record.year, record.day, record.part, status);
+            } else {
+                rows.push(Row::new(&record, answers.get(record.part)));
+            }
+        });
+        anyhow::Ok(())
+    })?;
+    report::emit(format, &rows)?;
+    warn_running(running);
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {total} answers are wrong");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::{Answer, Solver};
+
+    use super::*;
+
+    /// Day whose first part ignores its cancellation token, the second one checking it.
+    struct Stub;
+
+    impl Solver for Stub {
+        fn part_one(&self) -> impl Answer {
+            thread::sleep(GRACE * 3);
+            1
+        }
+
+        fn part_two(&self) -> impl Answer {
+            let token = cancel::token();
+            while !token.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            solver::Fallible::<u8>(Err(token.failure()))
+        }
+    }
+
+    static STUB: Day = Day { year: 2015, day: 1, parse: |_| Ok(Box::new(Stub)), implementation: Stub::IMPLEMENTATION };
+
+    #[test]
+    fn timeouts() {
+        let timeout = Some(Duration::from_millis(10));
+        let one = &run_day(&STUB, "Cargo.toml", &[], &[Part::One], timeout)[0];
+        assert!(one.timed_out() && one.still_running);
+        let two = &run_day(&STUB, "Cargo.toml", &[], &[Part::Two], timeout)[0];
+        assert!(two.timed_out() && !two.still_running);
+    }
+
+    #[test]
+    fn catch_panic() {
+        let res = catch::<()>(|| panic!("boom"));
+        assert_eq!("panicked: boom", format!("{}", res.unwrap_err()));
+    }
+}
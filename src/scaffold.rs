@@ -0,0 +1,42 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use anyhow::{ensure, Context};
+
+use crate::registry;
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// Generates the module of a day from the template, creating its year directory if needed. The build script then
+/// declares and registers it.
+///
+/// Returns the created file.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> anyhow::Result<PathBuf> {
+    ensure!(registry::YEARS.contains(&year), "{} is not a supported year", year);
+    ensure!((1..=registry::DAYS_PER_YEAR).contains(&day), "{} is not a valid day", day);
+
+    let year_dir = root.join("src").join(format!("y{}", year));
+    let day_file = year_dir.join(format!("d{:02}.rs", day));
+    ensure!(!day_file.exists(), "{} already exists", day_file.display());
+    fs::create_dir_all(&year_dir).with_context(|| format!("unable to create {}", year_dir.display()))?;
+    fs::write(&day_file, TEMPLATE).with_context(|| format!("unable to write {}", day_file.display()))?;
+    Ok(day_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn day_file() -> anyhow::Result<()> {
+        let root = env::temp_dir().join(format!("advent-of-code-scaffold-{}", std::process::id()));
+        let file = scaffold(&root, 2016, 5)?;
+        assert_eq!(root.join("src/y2016/d05.rs"), file);
+        assert_eq!(TEMPLATE, fs::read_to_string(&file)?);
+        assert!(scaffold(&root, 2016, 5).is_err());
+        assert!(scaffold(&root, 2016, 26).is_err());
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
@@ -1,6 +1,257 @@
-use std::fmt::Display;
+use std::{fmt::Display, io::{BufRead, Lines}};
+
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 pub trait Solver {
-    fn part_one(self: &Self) -> impl Display;
-    fn part_two(self: &Self) -> impl Display;
+    /// How much of each part is computed, in the order of the parts.
+    const IMPLEMENTATION: [Implementation; 2] = [Implementation::Complete; 2];
+    /// Parameters left implicit by the puzzle, such as a grid size or a number of steps.
+    const PARAMS: &'static [Param] = &[];
+
+    fn part_one(self: &Self) -> impl Answer;
+    fn part_two(self: &Self) -> impl Answer;
+
+    /// Overrides one of the declared [`Solver::PARAMS`], `value` being of the same kind as its default. Other names
+    /// are left to [`unknown_param`].
+    fn set(&mut self, name: &str, value: Value) -> anyhow::Result<()> {
+        let _ = value;
+        unknown_param(name)
+    }
+
+    /// Computes the state shared by both parts ahead of them, so it is built once per run and timed on its own.
+    /// Such state is kept in a `OnceLock` initialized on first use, parts solved on their own still getting it.
+    fn prepare(&self) {}
+}
+
+/// Default answer of [`Solver::set`] to a parameter it does not declare.
+pub fn unknown_param(name: &str) -> anyhow::Result<()> {
+    anyhow::bail!("unknown parameter {}", name)
+}
+
+/// How much of a part the solver actually computes, so genuine answers can be told apart from the other ones.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Implementation {
+    #[default]
+    Complete,
+    /// The answer was found by hand and is only checked against the input.
+    Hardcoded,
+    /// The part only reports [`Error::NotImplemented`].
+    Missing,
+}
+
+impl Display for Implementation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Implementation::Complete => "complete",
+            Implementation::Hardcoded => "hardcoded",
+            Implementation::Missing => "missing",
+        })
+    }
+}
+
+/// Reason why a part could not be solved.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("no solution found")]
+    NoSolution,
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    #[error("not implemented")]
+    NotImplemented,
+    #[error("timed out")]
+    Timeout,
+}
+
+/// Result of a part, anything displayable being a successful one.
+pub trait Answer {
+    fn answer(self) -> Result<String, Error>;
+}
+
+impl<T: Display> Answer for T {
+    fn answer(self) -> Result<String, Error> {
+        Ok(self.to_string())
+    }
+}
+
+/// Answer of a part which may fail, as a plain `Result` cannot be told apart from a displayable value.
+#[derive(Debug)]
+pub struct Fallible<T>(pub Result<T, Error>);
+
+impl<T: Display> Answer for Fallible<T> {
+    fn answer(self) -> Result<String, Error> {
+        self.0.map(|v| v.to_string())
+    }
+}
+
+/// Value of a parameter, pairs being written `x,y` on the command line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Pair(i64, i64),
+}
+
+impl Value {
+    /// Parses `s` as a value of the same kind as `self`.
+    pub fn parse_as(self, s: &str) -> anyhow::Result<Self> {
+        Ok(match self {
+            Self::Int(_) => Self::Int(s.trim().parse()?),
+            Self::Pair(..) => {
+                let (x, y) = s.split_once(',').with_context(|| format!("expected a pair x,y instead of {}", s))?;
+                Self::Pair(x.trim().parse()?, y.trim().parse()?)
+            },
+        })
+    }
+
+    pub fn int<T: TryFrom<i64>>(self) -> anyhow::Result<T> where T::Error: std::error::Error + Send + Sync + 'static {
+        match self {
+            Self::Int(v) => Ok(T::try_from(v)?),
+            v => anyhow::bail!("expected an integer instead of {}", v),
+        }
+    }
+
+    pub fn pair<T: TryFrom<i64>>(self) -> anyhow::Result<(T, T)> where T::Error: std::error::Error + Send + Sync + 'static {
+        match self {
+            Self::Pair(x, y) => Ok((T::try_from(x)?, T::try_from(y)?)),
+            v => anyhow::bail!("expected a pair instead of {}", v),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(v) => write!(f, "{}", v),
+            Self::Pair(x, y) => write!(f, "{},{}", x, y),
+        }
+    }
+}
+
+pub struct Param {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub default: Value,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Part {
+    #[value(help = "compute part one")]
+    One,
+    #[value(help = "compute part two")]
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn index(self) -> usize {
+        match self {
+            Part::One => 0,
+            Part::Two => 1,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Part::One => "one",
+            Part::Two => "two",
+        })
+    }
+}
+
+/// Parts to compute in a single run, the input being parsed once for all of them.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Parts {
+    #[value(help = "compute part one")]
+    One,
+    #[value(help = "compute part two")]
+    Two,
+    #[default]
+    #[value(help = "compute both parts, sharing the prepared state")]
+    Both,
+}
+
+impl Parts {
+    pub fn parts(self) -> &'static [Part] {
+        match self {
+            Parts::One => &[Part::One],
+            Parts::Two => &[Part::Two],
+            Parts::Both => &Part::ALL,
+        }
+    }
+}
+
+/// Object safe counterpart of [`Solver`] so parsed problems of different days can be handled alike.
+pub trait DynSolver {
+    fn solve(&self, part: Part) -> Result<String, Error>;
+    /// Parses `value` according to the declared parameter `name` then overrides it.
+    fn set(&mut self, name: &str, value: &str) -> anyhow::Result<()>;
+    fn prepare(&self);
+}
+
+impl<S: Solver> DynSolver for S {
+    fn solve(&self, part: Part) -> Result<String, Error> {
+        match part {
+            Part::One => self.part_one().answer(),
+            Part::Two => self.part_two().answer(),
+        }
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        let Some(param) = S::PARAMS.iter().find(|p| p.name == name) else {
+            let params = S::PARAMS.iter().map(|p| format!("\n  {}={} ({})", p.name, p.default, p.help)).collect::<String>();
+            anyhow::bail!("unknown parameter {}, available ones are:{}", name, if params.is_empty() { " none" } else { &params });
+        };
+        let value = param.default.parse_as(value).with_context(|| format!("invalid value for {}", name))?;
+        Solver::set(self, name, value)
+    }
+
+    fn prepare(&self) {
+        Solver::prepare(self)
+    }
+}
+
+/// Construction of a problem from the whole input kept in memory, which it may borrow from rather than copy.
+/// Problems parsed line by line through `TryFrom<Lines<B>>` get it for free.
+pub trait FromInput<'a>: Sized {
+    fn from_input(input: &'a str) -> anyhow::Result<Self>;
+}
+
+impl<P> FromInput<'_> for P
+where
+    P: for<'b> TryFrom<Lines<&'b [u8]>, Error = anyhow::Error>,
+{
+    fn from_input(input: &str) -> anyhow::Result<Self> {
+        P::try_from(input.as_bytes().lines())
+    }
+}
+
+pub fn parse<'a, P>(input: &'a str) -> anyhow::Result<Box<dyn DynSolver + 'a>>
+where
+    P: Solver + FromInput<'a> + 'a,
+{
+    let problem = P::from_input(input).map_err(|e| Error::InvalidInput(format!("{:#}", e)))?;
+    Ok(Box::new(problem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() -> anyhow::Result<()> {
+        assert_eq!(Value::Int(-3), Value::Int(0).parse_as("-3")?);
+        assert_eq!(Value::Pair(11, 7), Value::Pair(0, 0).parse_as("11, 7")?);
+        assert!(Value::Pair(0, 0).parse_as("11").is_err());
+        assert_eq!((11usize, 7usize), Value::Pair(11, 7).pair()?);
+        assert!(Value::Int(-1).int::<usize>().is_err());
+        assert!(Value::Int(1).pair::<usize>().is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,64 @@
+use std::{fmt::Arguments, sync::atomic::{AtomicU8, Ordering}};
+
+/// Verbosity of the debug output of solvers, which goes to stderr so answers printed on stdout stay untouched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Summaries such as intermediate results, shown with `-v`.
+    Debug = 1,
+    /// Details such as the state after each step, shown with `-vv`.
+    Trace = 2,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the verbosity from the number of `-v` flags, solvers being silent by default.
+pub fn set_verbosity(count: u8) {
+    VERBOSITY.store(count, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    VERBOSITY.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Prints a message of the solver defined in `module`, prefixed by its path within the crate.
+pub fn emit(module: &str, args: Arguments) {
+    let module = module.strip_prefix(concat!(env!("CARGO_CRATE_NAME"), "::")).unwrap_or(module);
+    eprintln!("[{}] {}", module, args);
+}
+
+/// Prints a debug message of the current solver with `-v`, the arguments being only evaluated then.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::Debug) {
+            $crate::trace::emit(module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+/// Prints a detailed message of the current solver with `-vv`, the arguments being only evaluated then.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::Trace) {
+            $crate::trace::emit(module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+pub(crate) use {debug, trace};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        assert!(Level::Debug < Level::Trace);
+        assert!(!enabled(Level::Debug));
+        let mut evaluated = false;
+        debug!("{}", {
+            evaluated = true;
+            "silent"
+        });
+        assert!(!evaluated);
+    }
+}
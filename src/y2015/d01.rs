@@ -26,7 +26,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().map(|i| {
             match i {
                 Parenthesis::Open => 1,
@@ -35,7 +35,7 @@ impl solver::Solver for Problem {
         }).sum::<i64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let r = self.0.iter().map(|i| {
             match i {
                 Parenthesis::Open => 1,
@@ -59,7 +59,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -75,7 +75,7 @@ mod tests {
     #[test_case(")())())", "-3"; "coccocc")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -83,7 +83,7 @@ mod tests {
     #[test_case("()())", "5"; "ococc")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
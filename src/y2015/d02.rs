@@ -22,14 +22,14 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().map(|&(l, w, h)| {
             let sides = [l*w, l*h, w*h];
             sides.iter().sum::<u64>()*2 + sides.iter().min().unwrap()
         }).sum::<u64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         self.0.iter().map(|&(l, w, h)| {
             let length = [l, w, h];
             (length.iter().sum::<u64>() - length.iter().max().unwrap())*2 + length.iter().product::<u64>()
@@ -41,7 +41,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -50,7 +50,7 @@ mod tests {
     #[test_case("1x1x10", "43")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -58,7 +58,7 @@ mod tests {
     #[test_case("1x1x10", "14")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -17,7 +17,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let mut m = HashMap::from([(Pt(0, 0), 1)]);
     
         self.0.iter().fold((0, 0).into(), |pos, &v| {
@@ -29,7 +29,7 @@ impl solver::Solver for Problem {
         m.values().filter(|&&l| l > 0).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut m = HashMap::from([(Pt(0, 0), 2)]);
 
         let (f, s) = self.0.iter().enumerate().partition::<Vec<(usize, _)>, _>(|(i, _)| i % 2 == 0);
@@ -54,7 +54,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -64,7 +64,7 @@ mod tests {
     #[test_case("^v^v^v^v^v", "2")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -73,7 +73,7 @@ mod tests {
     #[test_case("^v^v^v^v^v", "11")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -1,5 +1,5 @@
 use std::io::{BufRead, Lines};
-use crate::solver;
+use crate::{cancel, solver};
 use anyhow::Result;
 use md5;
 
@@ -14,26 +14,28 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        (1_u64..).find(|i| {
+    fn part_one(self: &Self) -> impl solver::Answer {
+        let token = cancel::token();
+        solver::Fallible((1_u64..).take_while(|_| !token.is_cancelled()).find(|i| {
             let digest = md5::compute(self.0.clone() + &i.to_string());
             let hash = format!("{:x}", digest);
             hash.starts_with("00000")
-        }).unwrap()
+        }).ok_or_else(|| token.failure()))
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        (1_u64..).find(|i| {
+    fn part_two(self: &Self) -> impl solver::Answer {
+        let token = cancel::token();
+        solver::Fallible((1_u64..).take_while(|_| !token.is_cancelled()).find(|i| {
             let digest = md5::compute(self.0.clone() + &i.to_string());
             let hash = format!("{:x}", digest);
             hash.starts_with("000000")
-        }).unwrap()
+        }).ok_or_else(|| token.failure()))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -42,7 +44,7 @@ mod tests {
     #[test_case("pqrstuv", "1048970")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
 }
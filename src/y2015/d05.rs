@@ -14,7 +14,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().filter(|&s| {
             s.chars().filter(|&c| c == 'a' || c == 'e' || c == 'i' || c == 'o' || c == 'u').count() >= 3
         }).filter(|&s| {
@@ -24,7 +24,7 @@ impl solver::Solver for Problem {
         }).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         self.0.iter().filter(|&s| {
             s.chars()
                 .zip(s.chars().skip(1))
@@ -44,7 +44,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -56,7 +56,7 @@ mod tests {
     #[test_case("dvszwmarrgswjxmb", "0")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -66,7 +66,7 @@ mod tests {
     #[test_case("ieodomkazucvgmuy", "0")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -70,7 +70,7 @@ impl Grid<u64> {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let mut grid = Grid::new::<1000, 1000>(false);
 
         self.0.iter().for_each(|&action| {
@@ -79,7 +79,7 @@ impl solver::Solver for Problem {
         grid.iter().filter(|&&l| l).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut grid = Grid::new::<1000, 1000>(0);
 
         self.0.iter().for_each(|&action| {
@@ -91,7 +91,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -101,7 +101,7 @@ mod tests {
     #[test_case("turn off 499,499 through 500,500", "0")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -109,7 +109,7 @@ mod tests {
     #[test_case("toggle 0,0 through 999,999", "2000000")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -1,24 +1,24 @@
-use std::{collections::HashMap, io::{BufRead, Lines}};
-use crate::solver;
-use anyhow::{anyhow, bail, Result};
+use std::collections::HashMap;
+use crate::{parse, solver};
+use anyhow::{bail, Result};
 
-#[derive(Clone)]
-enum Signal {
+#[derive(Clone, Copy)]
+enum Signal<'a> {
     Value(u16),
-    Wire(String),
+    Wire(&'a str),
 }
-impl TryFrom<&str> for Signal {
+impl<'a> TryFrom<&'a str> for Signal<'a> {
     type Error = anyhow::Error;
 
-    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: &'a str) -> std::result::Result<Self, Self::Error> {
         match value.parse() {
             Ok(v) => Ok(Self::Value(v)),
-            Err(_) => Ok(Self::Wire(value.to_owned())),
+            Err(_) => Ok(Self::Wire(value)),
         }
     }
 }
-impl Signal {
-    fn compute(&self, signals: &mut Circuit) -> u16 {
+impl<'a> Signal<'a> {
+    fn compute(&self, signals: &mut Circuit<'a, '_>) -> u16 {
         match self {
             Self::Value(v) => *v,
             Self::Wire(w) => signals.compute(w),
@@ -26,16 +26,16 @@ impl Signal {
     }
 }
 
-#[derive(Clone)]
-enum Gate {
-    And(Signal, Signal),
-    Or(Signal, Signal),
-    LShift(Signal, Signal),
-    RShift(Signal, Signal),
-    Not(Signal),
+#[derive(Clone, Copy)]
+enum Gate<'a> {
+    And(Signal<'a>, Signal<'a>),
+    Or(Signal<'a>, Signal<'a>),
+    LShift(Signal<'a>, Signal<'a>),
+    RShift(Signal<'a>, Signal<'a>),
+    Not(Signal<'a>),
 }
-impl Gate {
-    fn compute(&self, signals: &mut Circuit) -> u16 {
+impl<'a> Gate<'a> {
+    fn compute(&self, signals: &mut Circuit<'a, '_>) -> u16 {
         match self {
             Gate::And(signal, signal1) => signal.compute(signals) & signal1.compute(signals),
             Gate::Or(signal, signal1) => signal.compute(signals) | signal1.compute(signals),
@@ -45,10 +45,10 @@ impl Gate {
         }
     }
 }
-impl TryFrom<&str> for Gate {
+impl<'a> TryFrom<&'a str> for Gate<'a> {
     type Error = anyhow::Error;
     
-    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: &'a str) -> std::result::Result<Self, Self::Error> {
         let ops = value.split_whitespace().collect::<Vec<_>>();
         match ops.len() {
             2 if ops[0] == "NOT" => Ok(Self::Not(ops[1].try_into()?)),
@@ -61,23 +61,23 @@ impl TryFrom<&str> for Gate {
     }
 }
 
-#[derive(Clone)]
-enum Wire {
-    Signal(Signal),
-    Gate(Gate),
+#[derive(Clone, Copy)]
+enum Wire<'a> {
+    Signal(Signal<'a>),
+    Gate(Gate<'a>),
 }
-impl Wire {
-    fn compute(&self, signals: &mut Circuit) -> u16 {
+impl<'a> Wire<'a> {
+    fn compute(&self, signals: &mut Circuit<'a, '_>) -> u16 {
         match self {
             Wire::Signal(signal) => signal.compute(signals),
             Wire::Gate(gate) => gate.compute(signals),
         }
     }
 }
-impl TryFrom<&str> for Wire {
+impl<'a> TryFrom<&'a str> for Wire<'a> {
     type Error = anyhow::Error;
 
-    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: &'a str) -> std::result::Result<Self, Self::Error> {
         if value.contains(" ") {
             Ok(Self::Gate(value.try_into()?))
         } else {
@@ -99,13 +99,12 @@ impl TryFrom<&str> for Wire {
 //     }
 // }
 
-#[derive(Clone)]
-struct Circuit {
-    wires: HashMap<String, Wire>,
-    cache: HashMap<String, u16>,
+struct Circuit<'a, 'w> {
+    wires: &'w HashMap<&'a str, Wire<'a>>,
+    cache: HashMap<&'a str, u16>,
 }
-impl Circuit {
-    fn new(wires: HashMap<String, Wire>) -> Self {
+impl<'a, 'w> Circuit<'a, 'w> {
+    fn new(wires: &'w HashMap<&'a str, Wire<'a>>) -> Self {
         Self {
             wires,
             cache: HashMap::new(),
@@ -116,44 +115,41 @@ impl Circuit {
         self.cache.clear();
     }
 
-    fn set(&mut self, name: &str, value: u16) {
-        self.cache.insert(name.to_string(), value);
+    fn set(&mut self, name: &'a str, value: u16) {
+        self.cache.insert(name, value);
     }
 
-    fn compute(&mut self, name: &str) -> u16 {
+    fn compute(&mut self, name: &'a str) -> u16 {
         match self.cache.get(name) {
             Some(v) => *v,
             None => {
-                let res = self.wires.get(name).unwrap().clone().compute(self);
-                self.cache.insert(name.to_string(), res);
+                let res = self.wires.get(name).unwrap().compute(self);
+                self.cache.insert(name, res);
                 res
             },
         }
     }
 }
 
-pub struct Problem(HashMap<String, Wire>);
+pub struct Problem<'a>(HashMap<&'a str, Wire<'a>>);
 
-impl<B: BufRead> TryFrom<Lines<B>> for Problem {
-    type Error = anyhow::Error;
-
-    fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        Ok(Self(HashMap::from_iter(value.map(|l| {
-            let l = l?;
-            let (gate, name) = l.split_once(" -> ").ok_or_else(|| anyhow!("missing arrow of the instruction"))?;
-            anyhow::Ok((name.to_owned(), gate.try_into()?))
-        }).collect::<Result<Vec<_>, _>>()?.into_iter())))
+impl<'a> solver::FromInput<'a> for Problem<'a> {
+    fn from_input(input: &'a str) -> Result<Self> {
+        Ok(Self(parse::lines(input).map(|l| {
+            let (gate, name) = l.split_once(" -> ")?;
+            anyhow::Ok((name.as_str(), gate.as_str().try_into()?))
+        }).collect::<Result<HashMap<_, _>, _>>()?))
     }
 }
 
-impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        let mut board = Circuit::new(self.0.clone());
+impl solver::Solver for Problem<'_> {
+    fn part_one(self: &Self) -> impl solver::Answer {
+        let mut board = Circuit::new(&self.0);
         board.compute("a")
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        let mut board = Circuit::new(self.0.clone());
+    fn part_two(self: &Self) -> impl solver::Answer {
+        let mut board = Circuit::new(&self.0);
         let val = board.compute("a");
         board.reset();
         board.set("b", val);
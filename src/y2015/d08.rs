@@ -13,7 +13,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().map(|s| {
             let code_len = s.len();
             let Some(s) = s.strip_prefix('"') else { panic!("invalid content"); };
@@ -33,7 +33,7 @@ impl solver::Solver for Problem {
         }).sum::<usize>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         self.0.iter().map(|s| {
             s.chars().map(|c| match c {
                 '\\' | '"' => 2,
@@ -45,7 +45,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -57,7 +57,7 @@ mod tests {
     #[test_case("\"mjt\\\\xe\\x85um\"", "6")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -67,7 +67,7 @@ mod tests {
     #[test_case("\"\\x27\"", "5")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
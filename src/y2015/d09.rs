@@ -21,14 +21,14 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let cities = self.0.keys().cloned().flat_map(|(a, b)| [a, b]).collect::<HashSet<_>>();
         cities.iter().permutations(cities.len())
             .map(|order| order.iter().zip(order.iter().skip(1)).map(|(&a, &b)| { self.0.get(&(a.clone(), b.clone())).unwrap_or(&0) }).sum::<u64>())
             .min().unwrap_or(0)
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let cities = self.0.keys().cloned().flat_map(|(a, b)| [a, b]).collect::<HashSet<_>>();
         cities.iter().permutations(cities.len())
             .map(|order| order.iter().zip(order.iter().skip(1)).map(|(&a, &b)| { self.0.get(&(a.clone(), b.clone())).unwrap_or(&0) }).sum::<u64>())
@@ -38,7 +38,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -47,7 +47,7 @@ mod tests {
         let pb: Problem = "London to Dublin = 464
 London to Belfast = 518
 Dublin to Belfast = 141".as_bytes().lines().try_into()?;
-        assert_eq!("605", format!("{}", pb.part_one()));
+        assert_eq!("605", pb.part_one().answer()?);
         Ok(())
     }
     
@@ -56,7 +56,7 @@ Dublin to Belfast = 141".as_bytes().lines().try_into()?;
         let pb: Problem = "London to Dublin = 464
 London to Belfast = 518
 Dublin to Belfast = 141".as_bytes().lines().try_into()?;
-        assert_eq!("982", format!("{}", pb.part_two()));
+        assert_eq!("982", pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -3,13 +3,15 @@ use crate::solver;
 use anyhow::Result;
 use itertools::Itertools;
 
+const ITERATIONS: usize = 40;
+
 pub struct Problem(String, usize);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        (value, 40).try_into()
+        (value, ITERATIONS).try_into()
     }
 }
 
@@ -22,7 +24,19 @@ impl<B: BufRead> TryFrom<(Lines<B>, usize)> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    const PARAMS: &'static [solver::Param] = &[
+        solver::Param { name: "iterations", help: "look-and-say rounds of part one", default: solver::Value::Int(ITERATIONS as i64) },
+    ];
+
+    fn set(&mut self, name: &str, value: solver::Value) -> anyhow::Result<()> {
+        match name {
+            "iterations" => self.1 = value.int()?,
+            _ => return solver::unknown_param(name),
+        }
+        Ok(())
+    }
+
+    fn part_one(self: &Self) -> impl solver::Answer {
         let init = self.0.chars().map(|c| (c as usize) - ('0' as usize)).collect::<Vec<_>>();
         (0..self.1).fold(init, |acc, _| {
             acc.into_iter().peekable().batching(|it| {
@@ -34,7 +48,7 @@ impl solver::Solver for Problem {
         }).len()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let init = self.0.chars().map(|c| (c as usize) - ('0' as usize)).collect::<Vec<_>>();
         (0..50).fold(init, |acc, _| {
             acc.into_iter().peekable().batching(|it| {
@@ -49,7 +63,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -62,7 +76,7 @@ mod tests {
     #[test_case("1", 5, "6")]
     fn part_one(example: &str, iter: usize, result: &str) -> anyhow::Result<()> {
         let pb: Problem = (example.as_bytes().lines(), iter).try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
 }
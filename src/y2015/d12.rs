@@ -31,7 +31,7 @@ fn process(value: &serde_json::Value) -> i64 {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.chars().peekable().batching(|it| {
             it.peeking_take_while(|&c| !c.is_digit(10) && c != '-').for_each(|_| {});
             let n = it.peeking_take_while(|&c| c.is_digit(10) || c == '-').collect::<String>();
@@ -39,14 +39,14 @@ impl solver::Solver for Problem {
         }).sum::<i64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         process(&serde_json::from_str(&self.0).unwrap())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -61,7 +61,7 @@ mod tests {
     #[test_case("{}", "0" ; "arr")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
 
@@ -71,7 +71,7 @@ mod tests {
     #[test_case("[1,\"red\",5]", "6")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
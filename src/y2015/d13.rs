@@ -20,7 +20,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let names = self.0.keys().flat_map(|(a, b)| [a.clone(), b.clone()]).collect::<HashSet<_>>();
         let changes = names.iter().combinations(2).flat_map(|v| {
             let change = self.0[&(v[0].clone(), v[1].clone())] + self.0[&(v[1].clone(), v[0].clone())];
@@ -31,7 +31,7 @@ impl solver::Solver for Problem {
         }).max().unwrap_or(0)
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let names = self.0.keys().flat_map(|(a, b)| [a.clone(), b.clone()]).collect::<HashSet<_>>();
         let changes = names.iter().combinations(2).flat_map(|v| {
             let change = self.0[&(v[0].clone(), v[1].clone())] + self.0[&(v[1].clone(), v[0].clone())];
@@ -46,7 +46,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -64,7 +64,7 @@ Carol would gain 55 happiness units by sitting next to David.
 David would gain 46 happiness units by sitting next to Alice.
 David would lose 7 happiness units by sitting next to Bob.
 David would gain 41 happiness units by sitting next to Carol.".as_bytes().lines().try_into()?;
-        assert_eq!("330", format!("{}", pb.part_one()));
+        assert_eq!("330", pb.part_one().answer()?);
         Ok(())
     }
 }
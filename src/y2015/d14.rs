@@ -33,13 +33,15 @@ impl TryFrom<&str> for Reindeer {
     }
 }
 
+const DURATION: u64 = 2503;
+
 pub struct Problem(HashMap<String, Reindeer>, u64);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        (value, 2503).try_into()
+        (value, DURATION).try_into()
     }
 }
 
@@ -55,13 +57,25 @@ impl<B: BufRead> TryFrom<(Lines<B>, u64)> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    const PARAMS: &'static [solver::Param] = &[
+        solver::Param { name: "duration", help: "race length in seconds", default: solver::Value::Int(DURATION as i64) },
+    ];
+
+    fn set(&mut self, name: &str, value: solver::Value) -> anyhow::Result<()> {
+        match name {
+            "duration" => self.1 = value.int()?,
+            _ => return solver::unknown_param(name),
+        }
+        Ok(())
+    }
+
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.values().map(|r| {
             r.position_at(self.1)
         }).max().unwrap()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut points = HashMap::<&String, u64>::new();
         for i in 1..=self.1 {
             let names = self.0.iter().max_set_by_key(|r| r.1.position_at(i));
@@ -75,7 +89,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -83,7 +97,7 @@ mod tests {
     fn part_one() -> anyhow::Result<()> {
         let pb: Problem = ("Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
 Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.".as_bytes().lines(), 1000).try_into()?;
-        assert_eq!("1120", format!("{}", pb.part_one()));
+        assert_eq!("1120", pb.part_one().answer()?);
         Ok(())
     }
 
@@ -91,7 +105,7 @@ Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.".as_b
     fn part_two() -> anyhow::Result<()> {
         let pb: Problem = ("Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
 Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.".as_bytes().lines(), 1000).try_into()?;
-        assert_eq!("689", format!("{}", pb.part_two()));
+        assert_eq!("689", pb.part_two().answer()?);
         Ok(())
     }
 }
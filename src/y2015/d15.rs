@@ -103,7 +103,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display { // TODO a bit long, can be optimized?
+    fn part_one(self: &Self) -> impl solver::Answer { // TODO a bit long, can be optimized?
         let ingrerdients = self.0.values().collect::<Vec<_>>();
         iter::repeat_n(0..=100_i64, self.0.len())
             .multi_cartesian_product()
@@ -116,7 +116,7 @@ impl solver::Solver for Problem {
             }).max().unwrap()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display { // TODO same as part one
+    fn part_two(self: &Self) -> impl solver::Answer { // TODO same as part one
         let ingrerdients = self.0.values().collect::<Vec<_>>();
         iter::repeat_n(0..=100_i64, self.0.len())
             .multi_cartesian_product()
@@ -135,7 +135,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -143,7 +143,7 @@ mod tests {
     fn part_one() -> anyhow::Result<()> {
         let pb: Problem = "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
 Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3".as_bytes().lines().try_into()?;
-        assert_eq!("62842880", format!("{}", pb.part_one()));
+        assert_eq!("62842880", pb.part_one().answer()?);
         Ok(())
     }
 
@@ -151,7 +151,7 @@ Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3".as_bytes(
     fn part_two() -> anyhow::Result<()> {
         let pb: Problem = "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
 Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3".as_bytes().lines().try_into()?;
-        assert_eq!("57600000", format!("{}", pb.part_two()));
+        assert_eq!("57600000", pb.part_two().answer()?);
         Ok(())
     }
 }
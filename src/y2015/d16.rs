@@ -83,7 +83,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().find_map(|(&id, sue)| {
             if sue.can_be(Sue {
                 children: Some(3),
@@ -104,7 +104,7 @@ impl solver::Solver for Problem {
         }).unwrap()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         self.0.iter().find_map(|(&id, sue)| {
             if sue.can_be_range(Sue {
                 children: Some(3),
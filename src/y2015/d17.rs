@@ -41,18 +41,18 @@ fn process_sizes(mut containers: &[usize], rest: usize) -> Vec<Vec<usize>> { //
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         process_sizes(&self.0, self.1).len()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         process_sizes(&self.0, self.1).iter().min_set_by_key(|s| s.len()).len()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -63,7 +63,7 @@ mod tests {
 10
 5
 5".as_bytes().lines(), 25).try_into()?;
-        assert_eq!("4", format!("{}", pb.part_one()));
+        assert_eq!("4", pb.part_one().answer()?);
         Ok(())
     }
 
@@ -74,7 +74,7 @@ mod tests {
 10
 5
 5".as_bytes().lines(), 25).try_into()?;
-        assert_eq!("3", format!("{}", pb.part_two()));
+        assert_eq!("3", pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -1,15 +1,17 @@
 use std::io::{BufRead, Lines};
-use crate::{map::{Grid, Pt}, solver};
-use anyhow::{anyhow, Result};
+use crate::{map::{Grid, Pt}, parse, solver};
+use anyhow::Result;
 
 
+const STEPS: usize = 100;
+
 pub struct Problem(Grid<bool>, usize);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        (value, 100).try_into()
+        (value, STEPS).try_into()
     }
 }
 
@@ -17,23 +19,34 @@ impl<B: BufRead> TryFrom<(Lines<B>, usize)> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: (Lines<B>, usize)) -> Result<Self, Self::Error> {
-        let grid = value.0
-            .collect::<Result<Vec<_>, _>>()?.into_iter()
-            .map(|s| {
-                anyhow::Ok(s.chars().map(|c| (c == '#').then(|| true).or_else(|| (c == '.').then(|| false)).ok_or_else(|| anyhow!("unknown element"))).collect::<Result<Vec<_>, _>>()?)
-            })
-            .collect::<Result<Grid<_>, _>>()?;
+        let (grid, []) = Grid::parse(parse::Text::read(value.0)?.lines(), [], |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
         Ok(Self(grid, value.1))
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    const PARAMS: &'static [solver::Param] = &[
+        solver::Param { name: "steps", help: "number of animation steps", default: solver::Value::Int(STEPS as i64) },
+    ];
+
+    fn set(&mut self, name: &str, value: solver::Value) -> anyhow::Result<()> {
+        match name {
+            "steps" => self.1 = value.int()?,
+            _ => return solver::unknown_param(name),
+        }
+        Ok(())
+    }
+
+    fn part_one(self: &Self) -> impl solver::Answer {
         let mut grid = self.0.clone();
         let mut tmp_grid = Grid::new_dyn(grid.size().0, grid.size().1, false);
         (0..self.1).for_each(|_| {
             grid.all_positions().for_each(|pt| {
-                let neigh = grid.neigbours(pt).filter(|&&v| v).count();
+                let neigh = grid.neighbours(pt).filter(|&&v| v).count();
                 tmp_grid[pt] = if grid[pt] {
                     neigh == 2 || neigh == 3
                 } else {
@@ -45,7 +58,7 @@ impl solver::Solver for Problem {
         grid.iter().filter(|&&v| v).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut grid = self.0.clone();
         let (w, h) = grid.size();
         let mut tmp_grid = Grid::new_dyn(w, h, false);
@@ -53,7 +66,7 @@ impl solver::Solver for Problem {
         broken_lights.iter().for_each(|&p| { grid[p] = true; });
         (0..self.1).for_each(|_| {
             grid.all_positions().for_each(|pt| {
-                let neigh = grid.neigbours(pt).filter(|&&v| v).count();
+                let neigh = grid.neighbours(pt).filter(|&&v| v).count();
                 tmp_grid[pt] = if grid[pt] {
                     neigh == 2 || neigh == 3
                 } else {
@@ -69,7 +82,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -81,7 +94,7 @@ mod tests {
 ..#...
 #.#..#
 ####..".as_bytes().lines(), 4).try_into()?;
-        assert_eq!("4", format!("{}", pb.part_one()));
+        assert_eq!("4", pb.part_one().answer()?);
         Ok(())
     }
 
@@ -93,7 +106,7 @@ mod tests {
 ..#...
 #.#..#
 ####..".as_bytes().lines(), 5).try_into()?;
-        assert_eq!("17", format!("{}", pb.part_two()));
+        assert_eq!("17", pb.part_two().answer()?);
         Ok(())
     }
 }
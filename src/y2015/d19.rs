@@ -1,5 +1,5 @@
 use std::{io::{BufRead, Lines}, iter};
-use crate::solver;
+use crate::{cancel, solver};
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
 
@@ -35,24 +35,25 @@ fn generated_molecules(molecule: &str, replacements: &[(String, String)]) -> Vec
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         generated_molecules(&self.1, &self.0).len()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let repls = self.0.iter().sorted_unstable_by_key(|(_, el)| el.chars().filter(|c| c.is_uppercase()).count()).rev().collect::<Vec<_>>();
+        let token = cancel::token();
         let mut seq = iter::successors(Some(self.1.clone()), |s| {
             repls.iter().find_map(|(a, b)| {
                 s.contains(b).then(|| s.replacen(b, &a, 1))
             })
-        });
-        seq.position(|el| el == "e").unwrap()
+        }).take_while(|_| !token.is_cancelled());
+        solver::Fallible(seq.position(|el| el == "e").ok_or_else(|| token.failure()))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -63,7 +64,7 @@ H => OH
 O => HH
 
 HOHOHO".as_bytes().lines().try_into()?;
-        assert_eq!("7", format!("{}", pb.part_one()));
+        assert_eq!("7", pb.part_one().answer()?);
         Ok(())
     }
 
@@ -76,7 +77,10 @@ H => OH
 O => HH
 
 HOHOHO".as_bytes().lines().try_into()?;
-        assert_eq!("6", format!("{}", pb.part_two()));
+        assert_eq!("6", pb.part_two().answer()?);
+
+        let pb: Problem = "H => HO\n\nHOH".as_bytes().lines().try_into()?;
+        assert_eq!(Err(solver::Error::NoSolution), pb.part_two().answer());
         Ok(())
     }
 }
@@ -1,5 +1,5 @@
 use std::io::{BufRead, Lines};
-use crate::solver;
+use crate::{cancel, solver};
 use anyhow::Result;
 
 pub struct Problem(usize);
@@ -26,31 +26,33 @@ fn seq_sqrt() -> impl Iterator<Item = (usize, usize)> {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        seq_sqrt().find_map(|(i, sqrt)| {
+    fn part_one(self: &Self) -> impl solver::Answer {
+        let token = cancel::token();
+        solver::Fallible(seq_sqrt().take_while(|_| !token.is_cancelled()).find_map(|(i, sqrt)| {
             let c = (1..=sqrt).filter(|j| i%j == 0).flat_map(|j| [j, i/j]).sum::<usize>()*10;
             (c > self.0).then_some(i)
-        }).unwrap()
+        }).ok_or_else(|| token.failure()))
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        seq_sqrt().find_map(|(house, sqrt_house)| {
+    fn part_two(self: &Self) -> impl solver::Answer {
+        let token = cancel::token();
+        solver::Fallible(seq_sqrt().take_while(|_| !token.is_cancelled()).find_map(|(house, sqrt_house)| {
             let c = (1..=sqrt_house).filter(|&elf| house%elf == 0).flat_map(|elf| [elf, house/elf]).filter(|&elf| elf >= house/50).sum::<usize>()*11;
             (c > self.0).then_some(house)
-        }).unwrap()
+        }).ok_or_else(|| token.failure()))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() -> anyhow::Result<()> {
         let pb: Problem = "100".as_bytes().lines().try_into()?;
-        assert_eq!("6", format!("{}", pb.part_one()));
+        assert_eq!("6", pb.part_one().answer()?);
         Ok(())
     }
 }
@@ -1,4 +1,4 @@
-use std::{io::{BufRead, Lines}, iter::Sum};
+use std::{io::{BufRead, Lines}, iter::Sum, sync::OnceLock};
 use crate::solver;
 use anyhow::{anyhow, Result};
 use itertools::{iproduct, Itertools};
@@ -30,7 +30,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Stats {
     }
 }
 
-pub struct Problem(Stats);
+pub struct Problem(Stats, OnceLock<Vec<Equipment>>);
 
 #[derive(Debug)]
 struct Equipment {
@@ -54,108 +54,78 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        Ok(Self(value.try_into()?))
+        Ok(Self(value.try_into()?, OnceLock::new()))
+    }
+}
+
+impl Problem {
+    /// Every allowed combination of items from the shop, cheapest first.
+    fn loadouts(&self) -> &Vec<Equipment> {
+        self.1.get_or_init(|| {
+            let weapons = vec![
+                Equipment { cost: 8, damage: 4, armor: 0 },
+                Equipment { cost: 10, damage: 5, armor: 0 },
+                Equipment { cost: 25, damage: 6, armor: 0 },
+                Equipment { cost: 40, damage: 7, armor: 0 },
+                Equipment { cost: 74, damage: 8, armor: 0 },
+            ];
+            let armors = vec![
+                Equipment { cost: 13, damage: 0, armor: 1 },
+                Equipment { cost: 31, damage: 0, armor: 2 },
+                Equipment { cost: 53, damage: 0, armor: 3 },
+                Equipment { cost: 75, damage: 0, armor: 4 },
+                Equipment { cost: 102, damage: 0, armor: 5 },
+            ];
+            let rings = vec![
+                Equipment { cost: 25, damage: 1, armor: 0 },
+                Equipment { cost: 50, damage: 2, armor: 0 },
+                Equipment { cost: 100, damage: 3, armor: 0 },
+                Equipment { cost: 20, damage: 0, armor: 1 },
+                Equipment { cost: 40, damage: 0, armor: 2 },
+                Equipment { cost: 80, damage: 0, armor: 3 },
+            ];
+            iproduct!(
+                weapons.iter().powerset().filter(|s| s.len() == 1),
+                armors.iter().powerset().filter(|s| s.len() <= 1),
+                rings.iter().powerset().filter(|s| s.len() <= 2),
+            )
+                .map(|(a, b, c)| [a, b, c].concat().into_iter().sum::<Equipment>())
+                .sorted_by_key(|eqs| eqs.cost)
+                .collect()
+        })
+    }
+
+    /// Whether the player wearing `eqs` defeats the boss.
+    fn wins(&self, eqs: &Equipment) -> bool {
+        let mut player = Stats {
+            hp: 100,
+            damage: eqs.damage,
+            armor: eqs.armor,
+        };
+        let mut boss = self.0.clone();
+        loop {
+            if player.attacks(&mut boss) {
+                return true;
+            }
+
+            if boss.attacks(&mut player) {
+                return false;
+            }
+        }
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        let weapons = vec![
-            Equipment { cost: 8, damage: 4, armor: 0 },
-            Equipment { cost: 10, damage: 5, armor: 0 },
-            Equipment { cost: 25, damage: 6, armor: 0 },
-            Equipment { cost: 40, damage: 7, armor: 0 },
-            Equipment { cost: 74, damage: 8, armor: 0 },
-        ];
-        let armors = vec![
-            Equipment { cost: 13, damage: 0, armor: 1 },
-            Equipment { cost: 31, damage: 0, armor: 2 },
-            Equipment { cost: 53, damage: 0, armor: 3 },
-            Equipment { cost: 75, damage: 0, armor: 4 },
-            Equipment { cost: 102, damage: 0, armor: 5 },
-        ];
-        let rings = vec![
-            Equipment { cost: 25, damage: 1, armor: 0 },
-            Equipment { cost: 50, damage: 2, armor: 0 },
-            Equipment { cost: 100, damage: 3, armor: 0 },
-            Equipment { cost: 20, damage: 0, armor: 1 },
-            Equipment { cost: 40, damage: 0, armor: 2 },
-            Equipment { cost: 80, damage: 0, armor: 3 },
-        ];
-        iproduct!(
-            weapons.iter().powerset().filter(|s| s.len() == 1),
-            armors.iter().powerset().filter(|s| s.len() <= 1),
-            rings.iter().powerset().filter(|s| s.len() <= 2),
-        )
-            .map(|(a, b, c)| [a, b, c].concat().into_iter().sum::<Equipment>())
-            .sorted_by_key(|eqs| eqs.cost)
-            .find(|eqs| {
-                let mut player = Stats {
-                    hp: 100,
-                    damage: eqs.damage,
-                    armor: eqs.armor,
-                };
-                let mut boss = self.0.clone();
-                loop {
-                    if player.attacks(&mut boss) {
-                        return true;
-                    }
-
-                    if boss.attacks(&mut player) {
-                        return false;
-                    }
-                }
-            }).map(|eqs| eqs.cost).unwrap()
+    fn prepare(&self) {
+        self.loadouts();
+    }
+
+    fn part_one(self: &Self) -> impl solver::Answer {
+        self.loadouts().iter().find(|eqs| self.wins(eqs)).map(|eqs| eqs.cost).unwrap()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        let weapons = vec![
-            Equipment { cost: 8, damage: 4, armor: 0 },
-            Equipment { cost: 10, damage: 5, armor: 0 },
-            Equipment { cost: 25, damage: 6, armor: 0 },
-            Equipment { cost: 40, damage: 7, armor: 0 },
-            Equipment { cost: 74, damage: 8, armor: 0 },
-        ];
-        let armors = vec![
-            Equipment { cost: 13, damage: 0, armor: 1 },
-            Equipment { cost: 31, damage: 0, armor: 2 },
-            Equipment { cost: 53, damage: 0, armor: 3 },
-            Equipment { cost: 75, damage: 0, armor: 4 },
-            Equipment { cost: 102, damage: 0, armor: 5 },
-        ];
-        let rings = vec![
-            Equipment { cost: 25, damage: 1, armor: 0 },
-            Equipment { cost: 50, damage: 2, armor: 0 },
-            Equipment { cost: 100, damage: 3, armor: 0 },
-            Equipment { cost: 20, damage: 0, armor: 1 },
-            Equipment { cost: 40, damage: 0, armor: 2 },
-            Equipment { cost: 80, damage: 0, armor: 3 },
-        ];
-        iproduct!(
-            weapons.iter().powerset().filter(|s| s.len() == 1),
-            armors.iter().powerset().filter(|s| s.len() <= 1),
-            rings.iter().powerset().filter(|s| s.len() <= 2),
-        )
-            .map(|(a, b, c)| [a, b, c].concat().into_iter().sum::<Equipment>())
-            .sorted_by_key(|eqs| eqs.cost)
-            .rev()
-            .find(|eqs| {
-                let mut player = Stats {
-                    hp: 100,
-                    damage: eqs.damage,
-                    armor: eqs.armor,
-                };
-                let mut boss = self.0.clone();
-                loop {
-                    if player.attacks(&mut boss) {
-                        return false;
-                    }
-
-                    if boss.attacks(&mut player) {
-                        return true;
-                    }
-                }
-            }).map(|eqs| eqs.cost).unwrap()
+    fn part_two(self: &Self) -> impl solver::Answer {
+        self.loadouts().iter().rev().find(|eqs| !self.wins(eqs)).map(|eqs| eqs.cost).unwrap()
     }
 }
 
@@ -16,7 +16,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let game = Game {
             player: Wizard::new(50, 500),
             enemy: self.0.clone(),
@@ -25,7 +25,7 @@ impl solver::Solver for Problem {
         game.process()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let game = Game {
             player: Wizard::new(50, 500),
             enemy: self.0.clone(),
@@ -129,13 +129,13 @@ impl Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let mut registries = HashMap::from([(Registry::A, 0_u64), (Registry::B, 0)]);
         self.process_instructions(&mut registries);
         registries[&self.1]
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut registries = HashMap::from([(Registry::A, 1_u64), (Registry::B, 0)]);
         self.process_instructions(&mut registries);
         registries[&self.1]
@@ -144,7 +144,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -154,7 +154,7 @@ mod tests {
 jio a, +2
 tpl a
 inc a".as_bytes().lines(), Registry::A).try_into()?;
-        assert_eq!("2", format!("{}", pb.part_one()));
+        assert_eq!("2", pb.part_one().answer()?);
         Ok(())
     }
 }
@@ -72,7 +72,7 @@ impl Ord for SubsetGroup {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let group_sum = self.0.iter().sum::<u64>()/3;
         let mut values = self.0.clone();
         values.sort();
@@ -82,7 +82,7 @@ impl solver::Solver for Problem {
         groups.iter().find(|l| groups.iter().any(|ll| ll.values.intersection(&l.values).next().is_none())).unwrap().product
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let group_sum = self.0.iter().sum::<u64>()/4;
         let mut values = self.0.clone();
         values.sort();
@@ -95,7 +95,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -111,7 +111,7 @@ mod tests {
 9
 10
 11".as_bytes().lines().try_into()?;
-        assert_eq!("99", format!("{}", pb.part_one()));
+        assert_eq!("99", pb.part_one().answer()?);
         Ok(())
     }
 }
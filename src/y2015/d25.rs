@@ -26,7 +26,9 @@ impl From<(usize, usize)> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    const IMPLEMENTATION: [solver::Implementation; 2] = [solver::Implementation::Complete, solver::Implementation::Missing];
+
+    fn part_one(self: &Self) -> impl solver::Answer {
         let l = self.0 + self.1 - 1;
         let l = (l-1)*l/2 + self.1 - 1;
         iter::successors(Some(20151125_u64), |n| {
@@ -34,21 +36,21 @@ impl solver::Solver for Problem {
         }).nth(l).unwrap()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        0
+    fn part_two(self: &Self) -> impl solver::Answer {
+        solver::Fallible::<u64>(Err(solver::Error::NotImplemented))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() -> anyhow::Result<()> {
         let pb: Problem = (4, 5).into();
-        assert_eq!("10600672", format!("{}", pb.part_one()));
+        assert_eq!("10600672", pb.part_one().answer()?);
         Ok(())
     }
 }
@@ -1,29 +1,6 @@
 use std::{collections::HashMap, io::{BufRead, Lines}};
-use crate::{map::{Direction as DirectionBase, Pt}, solver};
-use anyhow::{bail, Result};
-
-#[derive(Debug)]
-struct Direction(DirectionBase);
-
-impl From<DirectionBase> for Direction {
-    fn from(value: DirectionBase) -> Self {
-        Direction(value)
-    }
-}
-
-impl TryFrom<char> for Direction {
-    type Error = anyhow::Error;
-
-    fn try_from(value: char) -> std::result::Result<Self, Self::Error> {
-        Ok(match value {
-            'U' => DirectionBase::N,
-            'D' => DirectionBase::S,
-            'L' => DirectionBase::W,
-            'R' => DirectionBase::E,
-            e => bail!("invalid direction {e}"),
-        }.into())
-    }
-}
+use crate::{map::{Direction, Pt}, solver};
+use anyhow::Result;
 
 pub struct Problem(Vec<Vec<Direction>>);
 
@@ -39,16 +16,16 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().scan(Pt(1_usize, 1), |state, seq| {
-            *state = seq.into_iter().fold(*state, |acc, &Direction(dir)| {
+            *state = seq.into_iter().fold(*state, |acc, &dir| {
                 acc.saturating_add(dir, Pt(3, 3))
             });
             Some(*state)
         }).map(|Pt(x, y)| x + 1 + 3*y).map(|i| i.to_string()).collect::<String>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let keypad = HashMap::from([
                                               (Pt(2, 0), '1'),
                              (Pt(1, 1), '2'), (Pt(2, 1), '3'), (Pt(3, 1), '4'),
@@ -57,13 +34,8 @@ impl solver::Solver for Problem {
                                               (Pt(2, 4), 'D'),
         ]);
         self.0.iter().scan(Pt(0_usize, 2), |state, seq| {
-            *state = seq.into_iter().fold(*state, |acc, &Direction(dir)| {
-                let new = acc.saturating_add(dir, Pt(5, 5));
-                if keypad.contains_key(&new) {
-                    new
-                } else {
-                    acc
-                }
+            *state = seq.into_iter().fold(*state, |acc, &dir| {
+                acc.checked_add(dir).filter(|new| keypad.contains_key(new)).unwrap_or(acc)
             });
             Some(keypad[state])
         }).collect::<String>()
@@ -72,7 +44,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -83,7 +55,7 @@ LURDL
 UUUUD", "1985")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -93,7 +65,7 @@ LURDL
 UUUUD", "5DB3")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -67,13 +67,13 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().filter_map(|room| {
             room.is_checksum_valid().then_some(room.sector_id)
         }).sum::<u64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         self.0.iter().find_map(|room| {
             let name = room.decrypt_name();
             ["north", "pole", "object"].into_iter().all(|pat| name.contains(pat)).then_some(room.sector_id)
@@ -83,7 +83,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -94,7 +94,7 @@ not-a-real-room-404[oarel]", "1391")]
     #[test_case("totally-real-room-200[decoy]", "0")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
 }
@@ -1,2 +0,0 @@
-pub mod d01;
-pub mod d02;
@@ -14,7 +14,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let (twos, threes) = self.0.iter().fold((0, 0), |(twos, threes), s| {
             let counts = s.chars().fold(HashMap::new(), |mut count, val| {
                 count.entry(val).and_modify(|c| *c += 1).or_insert(1);
@@ -28,7 +28,7 @@ impl solver::Solver for Problem {
         twos*threes
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         self.0.iter().cartesian_product(self.0.iter()).find_map(|(a, b)| {
             if a == b {
                 return None;
@@ -48,7 +48,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -62,7 +62,7 @@ abcdee
 ababab", "12")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -75,7 +75,7 @@ axcye
 wvxyz", "fgij")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -36,7 +36,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().fold(Grid::new::<1000, 1000>(0), |mut grid, claim| {
             grid.items_mut(claim.start..(claim.start+claim.size)).for_each(|val| {
                 *val += 1;
@@ -45,7 +45,7 @@ impl solver::Solver for Problem {
         }).iter().filter(|&&v| v >= 2).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let grid = self.0.iter().fold(Grid::new::<1000, 1000>(0), |mut grid, claim| {
             grid.items_mut(claim.start..(claim.start+claim.size)).for_each(|val| {
                 *val += 1;
@@ -60,7 +60,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
     use test_case::test_case;
 
     use super::*;
@@ -70,7 +70,7 @@ mod tests {
 #3 @ 5,5: 2x2", "4")]
     fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_one()));
+        assert_eq!(result, pb.part_one().answer()?);
         Ok(())
     }
     
@@ -79,7 +79,7 @@ mod tests {
 #3 @ 5,5: 2x2", "3")]
     fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
         let pb: Problem = example.as_bytes().lines().try_into()?;
-        assert_eq!(result, format!("{}", pb.part_two()));
+        assert_eq!(result, pb.part_two().answer()?);
         Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, io::{BufRead, Lines}, iter::zip};
-use crate::solver;
-use anyhow::{anyhow, Result};
+use crate::{parse, solver};
+use anyhow::Result;
 
 pub struct Problem(Vec<u64>, Vec<u64>);
 
@@ -8,17 +8,13 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let (a, b): (Vec<_>, Vec<_>) = value
-            .map(|res| {
-                res.map_err(|err| anyhow!(err)).and_then(|m| {
-                    let s = m.trim();
-                    let res= s.split_whitespace().collect::<Vec<_>>();
-                    // if res.len() != 
-                    anyhow::ensure!(res.len() == 2, "must have 2 numbers, got {}", res.len());
-                    Ok((res[0].parse::<u64>()?, res[1].parse::<u64>()?))
-                })
+        let text = parse::Text::read(value)?;
+        let (a, b): (Vec<_>, Vec<_>) = text.lines()
+            .map(|l| match l.words::<u64>()?.as_slice() {
+                &[a, b] => Ok((a, b)),
+                res => Err(l.error(format!("must have 2 numbers, got {}", res.len()))),
             })
-            .collect::<Result<Vec<_>, anyhow::Error>>()?
+            .collect::<Result<Vec<_>, parse::Error>>()?
             .into_iter()
             .unzip();
         anyhow::ensure!(a.len() == b.len(), "lists have different lengths (a = {}, b = {})", a.len(), b.len());
@@ -27,7 +23,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let mut a = self.0.clone();
         let mut b = self.1.clone();
         a.sort_unstable();
@@ -37,7 +33,7 @@ impl solver::Solver for Problem {
             .sum::<u64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut counters = HashMap::new();
         for &el in &self.1 {
             counters.entry(el).and_modify(|e| *e += 1).or_insert(1);
@@ -48,17 +44,17 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("11", format!("{}", Problem(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]).part_one()));
+        assert_eq!(Ok("11".to_string()), Problem(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("31", format!("{}", Problem(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]).part_two()));
+        assert_eq!(Ok("31".to_string()), Problem(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]).part_two().answer());
     }
 }
@@ -1,6 +1,6 @@
 use std::io::{BufRead, Lines};
-use crate::solver;
-use anyhow::{anyhow, Result};
+use crate::{parse, solver};
+use anyhow::Result;
 
 pub struct Problem(Vec<Vec<u64>>);
 
@@ -8,17 +8,15 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let res = value
-            .map(|s| {
-                s.map_err(|err| anyhow!(err)).and_then(|s| {
-                    let row = s.trim()
-                        .split_whitespace()
-                        .map(|l| l.parse::<u64>().map_err(|err| anyhow!(err)))
-                        .collect::<Result<Vec<_>, Self::Error>>()?;
-                    anyhow::ensure!(row.len() > 1);
-                    Ok(row)
-                })
-            }).collect::<Result<Vec<_>, Self::Error>>()?;
+        let text = parse::Text::read(value)?;
+        let res = text.lines()
+            .map(|l| {
+                let row = l.words::<u64>()?;
+                if row.len() < 2 {
+                    return Err(l.error("expected at least two levels"));
+                }
+                Ok(row)
+            }).collect::<Result<Vec<_>, parse::Error>>()?;
         Ok(Self(res))
     }
 }
@@ -32,11 +30,11 @@ fn is_valid_report(report: &[u64]) -> bool {
 
 impl solver::Solver for Problem {
 
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().filter(|&l| is_valid_report(l)).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         self.0.iter().filter(|&l| {
             (0..l.len()).any(|i| {
                 let (f, s) = l.split_at(i);
@@ -49,31 +47,31 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("2", format!("{}", Problem(vec![
+        assert_eq!(Ok("2".to_string()), Problem(vec![
             vec![7, 6, 4, 2, 1],
             vec![1, 2, 7, 8, 9],
             vec![9, 7, 6, 2, 1],
             vec![1, 3, 2, 4, 5],
             vec![8, 6, 4, 4, 1],
             vec![1, 3, 6, 7, 9],
-        ]).part_one()));
+        ]).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("4", format!("{}", Problem(vec![
+        assert_eq!(Ok("4".to_string()), Problem(vec![
             vec![7, 6, 4, 2, 1],
             vec![1, 2, 7, 8, 9],
             vec![9, 7, 6, 2, 1],
             vec![1, 3, 2, 4, 5],
             vec![8, 6, 4, 4, 1],
             vec![1, 3, 6, 7, 9],
-        ]).part_two()));
+        ]).part_two().answer());
     }
 }
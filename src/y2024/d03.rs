@@ -13,12 +13,12 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let reg = regex::Regex::new(r"mul\((?<x>\d{1,3}),(?<y>\d{1,3})\)").unwrap();
         reg.captures_iter(&self.0).map(|capt| capt["x"].parse::<u64>().unwrap() * capt["y"].parse::<u64>().unwrap()).sum::<u64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let reg = regex::Regex::new(r"(?<typt>don)'t\(\)|(?<typ>do)\(\)|(?<typm>mul)\((?<x>\d{1,3}),(?<y>\d{1,3})\)").unwrap();
         let it = reg.captures_iter(&self.0);
         it.scan(true, |state, capt| {
@@ -43,17 +43,17 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("161", format!("{}", Problem("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))".to_owned()).part_one()));
+        assert_eq!(Ok("161".to_string()), Problem("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))".to_owned()).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("48", format!("{}", Problem("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))".to_owned()).part_two()));
+        assert_eq!(Ok("48".to_string()), Problem("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))".to_owned()).part_two().answer());
     }
 }
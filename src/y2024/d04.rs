@@ -15,7 +15,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let xs = self.0.iter().enumerate().flat_map(|(x, aa)| {
             aa.iter().enumerate().filter_map(move |(y, &aaa)| if aaa == 'X' { Some((x, y)) } else { None })
         }).collect::<Vec<_>>();
@@ -29,7 +29,7 @@ impl solver::Solver for Problem {
         })).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let xs = self.0.iter().enumerate().skip(1).take(self.0.len()-2).flat_map(|(x, aa)| {
             aa.iter().enumerate().skip(1).take(aa.len()-2).filter_map(move |(y, &aaa)| if aaa == 'A' { Some((x, y)) } else { None })
         }).collect::<Vec<_>>();
@@ -42,13 +42,13 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("18", format!("{}", Problem(vec![
+        assert_eq!(Ok("18".to_string()), Problem(vec![
             vec!['M', 'M', 'M', 'S', 'X', 'X', 'M', 'A', 'S', 'M'],
             vec!['M', 'S', 'A', 'M', 'X', 'M', 'S', 'M', 'S', 'A'],
             vec!['A', 'M', 'X', 'S', 'X', 'M', 'A', 'A', 'M', 'M'],
@@ -59,12 +59,12 @@ mod tests {
             vec!['S', 'A', 'X', 'A', 'M', 'A', 'S', 'A', 'A', 'A'],
             vec!['M', 'A', 'M', 'M', 'M', 'X', 'M', 'M', 'M', 'M'],
             vec!['M', 'X', 'M', 'X', 'A', 'X', 'M', 'A', 'S', 'X'],
-        ]).part_one()));
+        ]).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("9", format!("{}", Problem(vec![
+        assert_eq!(Ok("9".to_string()), Problem(vec![
             vec!['M', 'M', 'M', 'S', 'X', 'X', 'M', 'A', 'S', 'M'],
             vec!['M', 'S', 'A', 'M', 'X', 'M', 'S', 'M', 'S', 'A'],
             vec!['A', 'M', 'X', 'S', 'X', 'M', 'A', 'A', 'M', 'M'],
@@ -75,6 +75,6 @@ mod tests {
             vec!['S', 'A', 'X', 'A', 'M', 'A', 'S', 'A', 'A', 'A'],
             vec!['M', 'A', 'M', 'M', 'M', 'X', 'M', 'M', 'M', 'M'],
             vec!['M', 'X', 'M', 'X', 'A', 'X', 'M', 'A', 'S', 'X'],
-        ]).part_two()));
+        ]).part_two().answer());
     }
 }
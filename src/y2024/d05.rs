@@ -1,29 +1,31 @@
 use std::{cmp::Ordering, collections::HashSet, io::{BufRead, Lines}};
-use crate::solver;
-use anyhow::Result;
+use crate::{parse, solver};
+use anyhow::{bail, Result};
 
 pub struct Problem(Vec<(u64, u64)>, Vec<Vec<u64>>);
 
-fn parse_dependencies(s: &str) -> Result<(u64, u64), anyhow::Error> {
-    let op = s.split('|').collect::<Vec<_>>();
-    Ok((op[0].parse::<u64>()?, op[1].parse::<u64>()?))
+fn parse_dependencies(s: &parse::Span) -> Result<(u64, u64), parse::Error> {
+    let (before, after) = s.split_once("|")?;
+    Ok((before.parse::<u64>()?, after.parse::<u64>()?))
 }
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let a = value
-            .map::<Result<_, Self::Error>, _>(|s| Ok(s?)).collect::<Result<Vec<_>, _>>()?;
-        let mut a = a.iter();
-        let deps = a.by_ref().map_while(|m| if m.is_empty() { None } else { Some(parse_dependencies(m)) }).collect::<Result<Vec<_>, _>>()?;
-        let updates = a.map(|l| l.split(',').map(|o| Ok::<_, Self::Error>(o.parse::<u64>()?)).collect::<Result<Vec<_>, _>>()).collect::<Result<Vec<_>, _>>()?;
+        let text = parse::Text::read(value)?;
+        let sections = text.sections();
+        let [deps, updates] = sections.as_slice() else {
+            bail!("expected ordering rules and updates separated by a blank line");
+        };
+        let deps = deps.iter().map(parse_dependencies).collect::<Result<Vec<_>, _>>()?;
+        let updates = updates.iter().map(|l| l.list::<u64>(",")).collect::<Result<Vec<_>, _>>()?;
         Ok(Self(deps, updates))
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let h = HashSet::<&(u64, u64)>::from_iter(self.0.iter());
         self.1.iter().filter(|update| {
             let mut update = update.as_slice();
@@ -37,7 +39,7 @@ impl solver::Solver for Problem {
         }).map(|f| f[f.len()/2]).sum::<u64>()    
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let h = HashSet::<&(u64, u64)>::from_iter(self.0.iter());
         let updates = &self.1;
         updates.iter().filter(|update| {
@@ -63,13 +65,13 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("143", format!("{}", Problem(vec![
+        assert_eq!(Ok("143".to_string()), Problem(vec![
             (47,53),
             (97,13),
             (97,61),
@@ -98,12 +100,12 @@ mod tests {
             vec![75,97,47,61,53],
             vec![61,13,29],
             vec![97,13,75,29,47],
-        ]).part_one()));
+        ]).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("123", format!("{}", Problem(vec![
+        assert_eq!(Ok("123".to_string()), Problem(vec![
             (47,53),
             (97,13),
             (97,61),
@@ -132,6 +134,6 @@ mod tests {
             vec![75,97,47,61,53],
             vec![61,13,29],
             vec![97,13,75,29,47],
-        ]).part_two()));
+        ]).part_two().answer());
     }
 }
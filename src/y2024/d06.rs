@@ -1,59 +1,47 @@
-use std::{collections::HashSet, io::{BufRead, Lines}, ops::Add};
-use crate::solver;
-use anyhow::{anyhow, Result};
+use std::{collections::HashSet, io::{BufRead, Lines}};
+use crate::{map::{Direction, Grid, Pt, Side}, parse, solver};
+use anyhow::Result;
 use std::hash::Hash;
 
-pub struct Problem(Vec<Vec<bool>>, (usize, usize));
+pub struct Problem(Grid<bool>, Pt<usize>);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let a = value
-            .map::<Result<_, Self::Error>, _>(|s| Ok(s?.chars().collect::<Vec<_>>()))
-            .collect::<Result<Vec<_>, _>>()?;
-        let mut pos = a.iter().enumerate().flat_map(|(x, l)| l.iter().enumerate().map(move |(y, lll)| ((x, y), lll)));
-        let Some((pos, _)) = pos.find(|&(_, c)| *c == '^') else {
-            return Err(anyhow!("unable to find initial position"));
-        };
-        Ok(Self(a.into_iter().map(|l| l.into_iter().map(|ll| ll == '#').collect()).collect(), pos))
+        let (obstacles, [guard]) = Grid::parse(parse::Text::read(value)?.lines(), ['^'], |c| match c {
+            '#' => Some(true),
+            '.' | '^' => Some(false),
+            _ => None,
+        })?;
+        Ok(Self(obstacles, guard))
     }
 }
 
 struct Map {
-    obstacles: Vec<Vec<bool>>,
-    current_position: (usize, usize),
+    obstacles: Grid<bool>,
+    current_position: Pt<usize>,
     current_dir: Direction,
 }
 
 impl Map {
-    fn new(obstacles: Vec<Vec<bool>>, start: (usize, usize)) -> Self {
+    fn new(obstacles: Grid<bool>, start: Pt<usize>) -> Self {
         Self {
             obstacles,
             current_position: start,
-            current_dir: Direction::Up,
+            current_dir: Direction::N,
         }
     }
 }
 
 impl Iterator for Map {
-    type Item = ((usize, usize), Direction);
+    type Item = (Pt<usize>, Direction);
 
     fn next(&mut self) -> Option<Self::Item> {
         let dir = self.current_dir;
-        let (x, y) = self.current_position;
-        if match dir {
-            Direction::Up => x == 0,
-            Direction::Right => y == self.obstacles[0].len()-1,
-            Direction::Down => x == self.obstacles.len()-1,
-            Direction::Left => y == 0,
-        } {
-            return None;
-        }
-
-        let next = self.current_position + dir;
-        if self.obstacles[next.0][next.1] {
-            self.current_dir = dir.right();
+        let next = self.obstacles.step(self.current_position, dir)?;
+        if self.obstacles[next] {
+            self.current_dir = dir + Side::Right;
         } else {
             self.current_position = next;
         }
@@ -61,39 +49,6 @@ impl Iterator for Map {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Hash, Copy, Debug)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Direction {
-    fn right(self) -> Self {
-        match self {
-            Self::Up => Self::Right,
-            Self::Right => Self::Down,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-        }
-    }
-}
-
-impl Add<Direction> for (usize, usize) {
-    type Output = Self;
-
-    fn add(self, rhs: Direction) -> Self::Output {
-        let (x, y) = self;
-        match rhs {
-            Direction::Up => (x-1, y),
-            Direction::Right => (x, y+1),
-            Direction::Down => (x+1, y),
-            Direction::Left => (x, y-1),
-        }
-    }
-}
-
 struct CycleError;
 
 struct CycleDetector<T: Iterator<Item = I>, I: Eq + Hash> {
@@ -132,7 +87,7 @@ impl<T: Iterator<Item = I>, I: Eq + Hash + Clone> Iterator for CycleDetector<T,
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let map = Map::new(self.0.clone(), self.1);
         let path = CycleDetector::new(map).collect::<Result<Vec<_>, _>>();
         if let Ok(res) = path {
@@ -142,7 +97,7 @@ impl solver::Solver for Problem {
         }
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let map = Map::new(self.0.clone(), self.1);
         let path = if let Ok(res) = CycleDetector::new(map).collect::<Result<Vec<_>, _>>() {
             HashSet::<_>::from_iter(res.into_iter().map(|(pos, _)| pos))
@@ -150,9 +105,9 @@ impl solver::Solver for Problem {
             return 0;
         };
 
-        HashSet::<_>::from_iter(path.into_iter().filter(|&(x, y)| {
+        HashSet::<_>::from_iter(path.into_iter().filter(|&pos| {
             let mut obstacles = self.0.clone();
-            obstacles[x][y] = true;
+            obstacles[pos] = true;
             CycleDetector::new(Map::new(obstacles, self.1)).collect::<Result<Vec<_>, _>>().is_err()
         })).len()
     }
@@ -160,13 +115,13 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("41", format!("{}", Problem(vec![
+        assert_eq!(Ok("41".to_string()), Problem(vec![
             vec![false, false, false, false, true, false, false, false, false, false],
             vec![false, false, false, false, false, false, false, false, false, true],
             vec![false, false, false, false, false, false, false, false, false, false],
@@ -177,12 +132,12 @@ mod tests {
             vec![false, false, false, false, false, false, false, false, true, false],
             vec![true, false, false, false, false, false, false, false, false, false],
             vec![false, false, false, false, false, false, true, false, false, false],
-        ], (6, 4)).part_one()));
+        ].into_iter().collect(), Pt(4, 6)).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("6", format!("{}", Problem(vec![
+        assert_eq!(Ok("6".to_string()), Problem(vec![
             vec![false, false, false, false, true, false, false, false, false, false],
             vec![false, false, false, false, false, false, false, false, false, true],
             vec![false, false, false, false, false, false, false, false, false, false],
@@ -193,6 +148,6 @@ mod tests {
             vec![false, false, false, false, false, false, false, false, true, false],
             vec![true, false, false, false, false, false, false, false, false, false],
             vec![false, false, false, false, false, false, true, false, false, false],
-        ], (6, 4)).part_two()));
+        ].into_iter().collect(), Pt(4, 6)).part_two().answer());
     }
 }
@@ -49,13 +49,13 @@ fn can_compute_result_two(res: u64, acc: u64, ops: &[u64]) -> bool {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().filter(|&(res, nums)| {
             can_compute_result(*res, 0, nums)
         }).map(|&(res, _)| res).sum::<u64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         self.0.iter().filter(|&(res, nums)| {
             can_compute_result_two(*res, 0, nums)
         }).map(|&(res, _)| res).sum::<u64>()
@@ -64,13 +64,13 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("3749", format!("{}", Problem(vec![
+        assert_eq!(Ok("3749".to_string()), Problem(vec![
             (190, vec![10, 19]),
             (3267, vec![81, 40, 27]),
             (83, vec![17, 5]),
@@ -80,12 +80,12 @@ mod tests {
             (192, vec![17, 8, 14]),
             (21037, vec![9, 7, 18, 13]),
             (292, vec![11, 6, 16, 20]),
-        ]).part_one()));
+        ]).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("11387", format!("{}", Problem(vec![
+        assert_eq!(Ok("11387".to_string()), Problem(vec![
             (190, vec![10, 19]),
             (3267, vec![81, 40, 27]),
             (83, vec![17, 5]),
@@ -95,6 +95,6 @@ mod tests {
             (192, vec![17, 8, 14]),
             (21037, vec![9, 7, 18, 13]),
             (292, vec![11, 6, 16, 20]),
-        ]).part_two()));
+        ]).part_two().answer());
     }
 }
@@ -1,5 +1,5 @@
 use std::{collections::{HashMap, HashSet}, io::{BufRead, Lines}};
-use crate::solver;
+use crate::{solver, trace};
 use anyhow::Result;
 
 pub struct Problem((usize, usize), HashMap<char, HashSet<(usize, usize)>>);
@@ -23,7 +23,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let (xmax, ymax) = self.0;
         let positions = self.1.iter().flat_map(|(_, l)| {
             let positions = &l.into_iter().collect::<Vec<_>>();
@@ -56,7 +56,7 @@ impl solver::Solver for Problem {
         HashSet::<_>::from_iter(positions).into_iter().count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let (xmax, ymax) = self.0;
         let positions = self.1.iter().flat_map(|(_, l)| {
             let positions = &l.into_iter().collect::<Vec<_>>();
@@ -74,30 +74,32 @@ impl solver::Solver for Problem {
                 }
             }).collect::<Vec<_>>()
         });
-        dbg!(HashSet::<_>::from_iter(positions)).into_iter().count()
+        let antinodes = HashSet::<_>::from_iter(positions);
+        trace::trace!("antinodes: {:?}", antinodes);
+        antinodes.len()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("14", format!("{}", Problem((12, 12), HashMap::<_, _>::from([
+        assert_eq!(Ok("14".to_string()), Problem((12, 12), HashMap::<_, _>::from([
             ('0', HashSet::<_>::from([(1, 8), (2, 5), (3, 7), (4, 4)])),
             ('A', HashSet::<_>::from([(5, 6), (8, 8), (9, 9)])),
-        ])).part_one()));
+        ])).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("34", format!("{}", Problem((12, 12), HashMap::<_, _>::from([
+        assert_eq!(Ok("34".to_string()), Problem((12, 12), HashMap::<_, _>::from([
             // ('T', HashSet::<_>::from([(0, 0), (1, 3), (2, 1)])),
             ('0', HashSet::<_>::from([(1, 8), (2, 5), (3, 7), (4, 4)])),
             ('A', HashSet::<_>::from([(5, 6), (8, 8), (9, 9)])),
-        ])).part_two()));
+        ])).part_two().answer());
     }
 }
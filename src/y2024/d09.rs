@@ -26,7 +26,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let mut binding = self.0.iter().flat_map(|&(c, v)| iter::repeat(v).take(c)).collect::<Vec<_>>();
         let data = binding.as_mut_slice();
         let mut i = 0_usize;
@@ -48,7 +48,7 @@ impl solver::Solver for Problem {
         data.into_iter().enumerate().filter_map(|(i, v)| v.and_then(|v| Some(v*i))).sum::<usize>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut data = self.0.clone();
         let mut j = data.len()-1;
         let mut el = usize::MAX;
@@ -96,23 +96,23 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("1928", format!("{}", Problem(vec![
+        assert_eq!(Ok("1928".to_string()), Problem(vec![
             (2, Some(0)), (3, None), (3, Some(1)), (3, None), (1, Some(2)), (3, None), (3, Some(3)), (1, None), (2, Some(4)), (1, None),
             (4, Some(5)), (1, None), (4, Some(6)), (1, None), (3, Some(7)), (1, None), (4, Some(8)), (0, None), (2, Some(9)),
-        ]).part_one()));
+        ]).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("2858", format!("{}", Problem(vec![
+        assert_eq!(Ok("2858".to_string()), Problem(vec![
             (2, Some(0)), (3, None), (3, Some(1)), (3, None), (1, Some(2)), (3, None), (3, Some(3)), (1, None), (2, Some(4)), (1, None),
             (4, Some(5)), (1, None), (4, Some(6)), (1, None), (3, Some(7)), (1, None), (4, Some(8)), (0, None), (2, Some(9)),
-        ]).part_two()));
+        ]).part_two().answer());
     }
 }
@@ -1,94 +1,41 @@
-use std::{io::{BufRead, Lines}, usize};
-use crate::solver;
+use std::io::{BufRead, Lines};
+use crate::{map::{Grid, Pt}, parse, solver};
 use anyhow::Result;
 
-pub struct Problem(Vec<Vec<usize>>);
-
-fn to_usize(c: char) -> anyhow::Result<usize> {
-    c.to_digit(10).ok_or_else(|| anyhow::anyhow!("invalid number")).and_then(|l| Ok(l.try_into()?))
-}
+pub struct Problem(Grid<u32>);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let a = value
-            .map(|l| {
-                let chars = l?.chars().map(to_usize).collect::<Result<Vec<_>, _>>()?;
-                anyhow::Ok(chars)
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(Self(a))
+        let (heights, []) = Grid::parse(parse::Text::read(value)?.lines(), [], |c| c.to_digit(10))?;
+        Ok(Self(heights))
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        let (xmax, ymax) = (self.0.len(), self.0[0].len());
-        let zeros = self.0.iter().enumerate()
-            .flat_map(|(x, row)| row.iter().enumerate().filter_map(move |(y, c)| if *c == 0 { Some((x, y))} else { None }))
-            .collect::<Vec<_>>();
+    fn part_one(self: &Self) -> impl solver::Answer {
+        let zeros = self.0.all_positions().filter(|&pt| self.0[pt] == 0).collect::<Vec<_>>();
         zeros.into_iter().map(|pos| {
             let mut pos = vec![pos];
             for level in 1..=9 {
-                for (x, y) in pos.drain(..).collect::<Vec<_>>() {
-                    if let Some(x) = x.checked_sub(1) {
-                        if self.0[x][y] == level {
-                            pos.push((x, y));
-                        }
-                    }
-                    if let Some(y) = y.checked_sub(1) {
-                        if self.0[x][y] == level {
-                            pos.push((x, y));
-                        }
-                    }
-                    if let Some(x) = x.checked_add(1).filter(|v| *v < xmax) {
-                        if self.0[x][y] == level {
-                            pos.push((x, y));
-                        }
-                    }
-                    if let Some(y) = y.checked_add(1).filter(|v| *v < ymax) {
-                        if self.0[x][y] == level {
-                            pos.push((x, y));
-                        }
-                    }
+                for pt in pos.drain(..).collect::<Vec<_>>() {
+                    pos.extend(self.0.orthogonal_neighbours(pt).map(|(next, _)| next).filter(|&next| self.0[next] == level));
                 }
-                pos.sort();
+                pos.sort_by_key(|&Pt(x, y)| (y, x));
                 pos.dedup();
             }
             pos.len()
         }).sum::<usize>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        let (xmax, ymax) = (self.0.len(), self.0[0].len());
-        let zeros = self.0.iter().enumerate()
-            .flat_map(|(x, row)| row.iter().enumerate().filter_map(move |(y, c)| if *c == 0 { Some((x, y))} else { None }))
-            .collect::<Vec<_>>();
+    fn part_two(self: &Self) -> impl solver::Answer {
+        let zeros = self.0.all_positions().filter(|&pt| self.0[pt] == 0).collect::<Vec<_>>();
         zeros.into_iter().map(|pos| {
             let mut pos = vec![pos];
             for level in 1..=9 {
-                for (x, y) in pos.drain(..).collect::<Vec<_>>() {
-                    if let Some(x) = x.checked_sub(1) {
-                        if self.0[x][y] == level {
-                            pos.push((x, y));
-                        }
-                    }
-                    if let Some(y) = y.checked_sub(1) {
-                        if self.0[x][y] == level {
-                            pos.push((x, y));
-                        }
-                    }
-                    if let Some(x) = x.checked_add(1).filter(|v| *v < xmax) {
-                        if self.0[x][y] == level {
-                            pos.push((x, y));
-                        }
-                    }
-                    if let Some(y) = y.checked_add(1).filter(|v| *v < ymax) {
-                        if self.0[x][y] == level {
-                            pos.push((x, y));
-                        }
-                    }
+                for pt in pos.drain(..).collect::<Vec<_>>() {
+                    pos.extend(self.0.orthogonal_neighbours(pt).map(|(next, _)| next).filter(|&next| self.0[next] == level));
                 }
             }
             pos.len()
@@ -98,13 +45,13 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("36", format!("{}", Problem(vec![
+        assert_eq!(Ok("36".to_string()), Problem(vec![
             vec![8, 9, 0, 1, 0, 1, 2, 3],
             vec![7, 8, 1, 2, 1, 8, 7, 4],
             vec![8, 7, 4, 3, 0, 9, 6, 5],
@@ -113,12 +60,12 @@ mod tests {
             vec![3, 2, 0, 1, 9, 0, 1, 2],
             vec![0, 1, 3, 2, 9, 8, 0, 1],
             vec![1, 0, 4, 5, 6, 7, 3, 2],
-        ]).part_one()));
+        ].into_iter().collect()).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("81", format!("{}", Problem(vec![
+        assert_eq!(Ok("81".to_string()), Problem(vec![
             vec![8, 9, 0, 1, 0, 1, 2, 3],
             vec![7, 8, 1, 2, 1, 8, 7, 4],
             vec![8, 7, 4, 3, 0, 9, 6, 5],
@@ -127,6 +74,6 @@ mod tests {
             vec![3, 2, 0, 1, 9, 0, 1, 2],
             vec![0, 1, 3, 2, 9, 8, 0, 1],
             vec![1, 0, 4, 5, 6, 7, 3, 2],
-        ]).part_two()));
+        ].into_iter().collect()).part_two().answer());
     }
 }
@@ -19,7 +19,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         (0..25).fold(self.0.clone(), |acc, _| {
             acc.iter().flat_map(|&i| {
                 if i == 0 {
@@ -39,7 +39,7 @@ impl solver::Solver for Problem {
         }).len()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let cache = &mut HashMap::<(u64, usize), usize>::new();
 
         fn compute(cache: &mut HashMap<(u64, usize), usize>, v: u64, rem_level: usize) -> usize {
@@ -76,13 +76,13 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("55312", format!("{}", Problem(vec![125, 17]).part_one()));
+        assert_eq!(Ok("55312".to_string()), Problem(vec![125, 17]).part_one().answer());
     }
 
     #[test]
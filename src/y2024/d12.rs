@@ -1,111 +1,85 @@
-use std::{collections::{HashMap, HashSet}, io::{BufRead, Lines}, usize};
-use crate::solver;
+use std::{collections::{HashMap, HashSet}, io::{BufRead, Lines}, sync::OnceLock, usize};
+use crate::{map::{Direction8, Grid, Pt}, parse, solver, trace};
 use anyhow::Result;
 
-pub struct Problem(Vec<Vec<char>>);
+/// Regions of garden plots, grouped by plant.
+type Regions = HashMap<char, Vec<HashSet<Pt<usize>>>>;
+
+pub struct Problem(Grid<char>, OnceLock<Regions>);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let a = value
-            .map(|l| {
-                let chars = l?.chars().collect::<Vec<_>>();
-                anyhow::Ok(chars)
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(Self(a))
+        let (plants, []) = Grid::parse(parse::Text::read(value)?.lines(), [], |c| c.is_ascii_uppercase().then_some(c))?;
+        Ok(Self(plants, OnceLock::new()))
     }
 }
 
-fn visit_zone(grid: &mut HashMap<(usize, usize), char>, zone: &mut HashSet<(usize, usize)>, pos: (usize, usize), n: char) {
-    if !grid.get(&pos).is_some_and(|&c| c == n) {
-        return;
-    }
-    grid.remove(&pos);
-    zone.insert(pos);
-
-    if pos.0 > 0 {
-        visit_zone(grid, zone, (pos.0-1, pos.1), n);
-    }
-    visit_zone(grid, zone, (pos.0+1, pos.1), n);
-    if pos.1 > 0 {
-        visit_zone(grid, zone, (pos.0, pos.1-1), n);
+impl Problem {
+    fn regions(&self) -> &Regions {
+        self.1.get_or_init(|| {
+            let mut zones = Regions::new();
+            let (w, h) = self.0.size();
+            let mut visited = Grid::new_dyn(w, h, false);
+            for pt in self.0.all_positions() {
+                if visited[pt] {
+                    continue;
+                }
+
+                let c = self.0[pt];
+                let mut zone = HashSet::from([pt]);
+                let mut pending = vec![pt];
+                visited[pt] = true;
+                while let Some(pos) = pending.pop() {
+                    for (next, _) in self.0.orthogonal_neighbours(pos) {
+                        if !visited[next] && self.0[next] == c {
+                            visited[next] = true;
+                            zone.insert(next);
+                            pending.push(next);
+                        }
+                    }
+                }
+                zones.entry(c).or_default().push(zone);
+            }
+            zones
+        })
     }
-    visit_zone(grid, zone, (pos.0, pos.1+1), n);
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        let zones = &mut HashMap::<char, Vec<HashSet<(usize, usize)>>>::new();
-
-        let grid = &mut HashMap::from_iter(self.0.iter()
-            .enumerate()
-            .flat_map(|(x, row)| row.iter().enumerate().map(move |(y, &c)| ((x, y), c))));
-        for x in 0..self.0.len() {
-            for y in 0..self.0[x].len() {
-                let Some(&c) = grid.get(&(x, y)) else {
-                    continue;
-                };
+    fn prepare(&self) {
+        self.regions();
+    }
 
-                let mut zone = &mut HashSet::<(usize, usize)>::new();
-                visit_zone(grid, &mut zone, (x, y), c);
-                zones.entry(c).and_modify(|zs| { zs.push(zone.clone()); }).or_insert_with(|| vec![zone.clone()]);
-            }
-        }
+    fn part_one(self: &Self) -> impl solver::Answer {
+        let zones = self.regions();
 
-        zones.iter().flat_map(|(c, zones)| zones.iter().map(|zone| {
-            let perimeter = zone.iter().map(|&loc| {
-                let mut c = 0;
-                if loc.0 == 0 || !zone.contains(&(loc.0-1, loc.1)) { c += 1 }
-                if loc.1 == 0 || !zone.contains(&(loc.0, loc.1-1)) { c += 1 }
-                if !zone.contains(&(loc.0+1, loc.1)) { c += 1 }
-                if !zone.contains(&(loc.0, loc.1+1)) { c += 1 }
-                c
-            }).sum::<usize>();
+        zones.iter().flat_map(|(c, zones)| zones.iter().map(move |zone| {
+            let perimeter = zone.iter()
+                .map(|&loc| 4 - self.0.orthogonal_neighbours(loc).filter(|(n, _)| zone.contains(n)).count())
+                .sum::<usize>();
             let area = zone.len();
-            dbg!(*c, perimeter, area);
+            trace::trace!("region {}: perimeter {}, area {}", c, perimeter, area);
             perimeter*area
         })).sum::<usize>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        let zones = &mut HashMap::<char, Vec<HashSet<(usize, usize)>>>::new();
-
-        let grid = &mut HashMap::from_iter(self.0.iter()
-            .enumerate()
-            .flat_map(|(x, row)| row.iter().enumerate().map(move |(y, &c)| ((x, y), c))));
-        for x in 0..self.0.len() {
-            for y in 0..self.0[x].len() {
-                let Some(&c) = grid.get(&(x, y)) else {
-                    continue;
-                };
-
-                let mut zone = &mut HashSet::<(usize, usize)>::new();
-                visit_zone(grid, &mut zone, (x, y), c);
-                zones.entry(c).and_modify(|zs| { zs.push(zone.clone()); }).or_insert_with(|| vec![zone.clone()]);
-            }
-        }
+    fn part_two(self: &Self) -> impl solver::Answer {
+        let zones = self.regions();
 
-        zones.iter().flat_map(|(c, zones)| zones.iter().map(|zone| {
+        zones.iter().flat_map(|(c, zones)| zones.iter().map(move |zone| {
+            // A region has as many sides as corners, each one found from the two sides and the diagonal around it.
             let sides = zone.iter().map(|&loc| {
-                let mut c = 0;
-                let has_top = loc.0 == 0 || !zone.contains(&(loc.0-1, loc.1));
-                let has_left = loc.1 == 0 || !zone.contains(&(loc.0, loc.1-1));
-                let has_bottom = !zone.contains(&(loc.0+1, loc.1));
-                let has_right = !zone.contains(&(loc.0, loc.1+1));
-                if has_top && has_left { c += 1 }
-                if has_top && has_right { c += 1 }
-                if has_bottom && has_left { c += 1 }
-                if has_bottom && has_right { c += 1 }
-                if !has_top && !has_left && !zone.contains(&(loc.0-1, loc.1-1)) { c += 1 }
-                if !has_top && !has_right && !zone.contains(&(loc.0-1, loc.1+1)) { c += 1 }
-                if !has_bottom && !has_left && !zone.contains(&(loc.0+1, loc.1-1)) { c += 1 }
-                if !has_bottom && !has_right && !zone.contains(&(loc.0+1, loc.1+1)) { c += 1 }
-                c
+                let around = self.0.all_neighbours(loc).filter(|(n, _)| zone.contains(n)).map(|(_, d)| d).collect::<Vec<_>>();
+                let has = |d| around.contains(&d);
+                use Direction8::*;
+                [(N, E, NE), (E, S, SE), (S, W, SW), (W, N, NW)].into_iter()
+                    .filter(|&(a, b, diag)| (!has(a) && !has(b)) || (has(a) && has(b) && !has(diag)))
+                    .count()
             }).sum::<usize>();
             let area = zone.len();
-            dbg!(*c, sides, area);
+            trace::trace!("region {}: {} sides, area {}", c, sides, area);
             sides*area
         })).sum::<usize>()
     }
@@ -113,13 +87,13 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("1930", format!("{}", Problem(vec![
+        assert_eq!(Ok("1930".to_string()), Problem(vec![
             vec!['R', 'R', 'R', 'R', 'I', 'I', 'C', 'C', 'F', 'F'],
             vec!['R', 'R', 'R', 'R', 'I', 'I', 'C', 'C', 'C', 'F'],
             vec!['V', 'V', 'R', 'R', 'R', 'C', 'C', 'F', 'F', 'F'],
@@ -130,12 +104,12 @@ mod tests {
             vec!['M', 'I', 'I', 'I', 'I', 'I', 'J', 'J', 'E', 'E'],
             vec!['M', 'I', 'I', 'I', 'S', 'I', 'J', 'E', 'E', 'E'],
             vec!['M', 'M', 'M', 'I', 'S', 'S', 'J', 'E', 'E', 'E'],
-        ]).part_one()));
+        ].into_iter().collect(), OnceLock::new()).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("1206", format!("{}", Problem(vec![
+        assert_eq!(Ok("1206".to_string()), Problem(vec![
             vec!['R', 'R', 'R', 'R', 'I', 'I', 'C', 'C', 'F', 'F'],
             vec!['R', 'R', 'R', 'R', 'I', 'I', 'C', 'C', 'C', 'F'],
             vec!['V', 'V', 'R', 'R', 'R', 'C', 'C', 'F', 'F', 'F'],
@@ -146,6 +120,6 @@ mod tests {
             vec!['M', 'I', 'I', 'I', 'I', 'I', 'J', 'J', 'E', 'E'],
             vec!['M', 'I', 'I', 'I', 'S', 'I', 'J', 'E', 'E', 'E'],
             vec!['M', 'M', 'M', 'I', 'S', 'S', 'J', 'E', 'E', 'E'],
-        ]).part_two()));
+        ].into_iter().collect(), OnceLock::new()).part_two().answer());
     }
 }
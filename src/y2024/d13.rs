@@ -29,7 +29,7 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let cost_a = 3;
         let cost_b = 1;
         self.0.iter().filter_map(|&([btn_a, btn_b], prize)| {
@@ -37,7 +37,7 @@ impl solver::Solver for Problem {
         }).sum::<i64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let cost_a = 3;
         let cost_b = 1;
         self.0.iter().filter_map(|&([btn_a, btn_b], prize)| {
@@ -56,18 +56,18 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("480", format!("{}", Problem(vec![
+        assert_eq!(Ok("480".to_string()), Problem(vec![
             ([(94, 34), (22, 67)], (8400, 5400)),
             ([(26, 66), (67, 21)], (12748, 12176)),
             ([(17, 86), (84, 37)], (7870, 6450)),
             ([(69, 23), (27, 71)], (18641, 10279)),
-        ]).part_one()));
+        ]).part_one().answer());
     }
 
     #[test]
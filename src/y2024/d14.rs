@@ -1,47 +1,59 @@
 use std::{collections::HashMap, io::{BufRead, Lines}};
-use crate::solver;
+use crate::{cancel, map::Pt, parse, solver, trace};
 use anyhow::Result;
 
-pub struct Problem(Vec<((i64, i64), (i64, i64))>, (i64, i64));
+const SIZE: (i64, i64) = (101, 103);
+
+/// Position and velocity of each robot, along with the size of the room.
+pub struct Problem(Vec<(Pt<i64>, Pt<i64>)>, Pt<i64>);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let a = value
+        let text = parse::Text::read(value)?;
+        let a = text.lines()
             .map(|l| {
-                let l = l?;
-                let (pos, speed) = l.split_once(' ').ok_or_else(|| anyhow::anyhow!("missing space delim"))?;
-                let pos = pos[2..].split_once(',').ok_or_else(|| anyhow::anyhow!("missing comma delim"))?;
-                let speed = speed[2..].split_once(',').ok_or_else(|| anyhow::anyhow!("missing comma delim"))?;
+                let (pos, speed) = l.split_once(" ")?;
+                let pos = pos.strip_prefix("p=")?.split_once(",")?;
+                let speed = speed.strip_prefix("v=")?.split_once(",")?;
 
-                anyhow::Ok(((pos.0.parse::<i64>()?, pos.1.parse::<i64>()?), (speed.0.parse::<i64>()?, speed.1.parse::<i64>()?)))
+                Ok((Pt(pos.0.parse::<i64>()?, pos.1.parse::<i64>()?), Pt(speed.0.parse::<i64>()?, speed.1.parse::<i64>()?)))
             })
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(Self(a, (101, 103)))
+            .collect::<Result<Vec<_>, parse::Error>>()?;
+        Ok(Self(a, SIZE.into()))
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        let (xmax, ymax) = self.1;
-        let pos = self.0.iter().map(|&((xpos, ypos), (xs, ys))| {
-            ((xpos + 100*xs).rem_euclid(xmax), (ypos + 100*ys).rem_euclid(ymax))
-        }).collect::<Vec<_>>();
+    const PARAMS: &'static [solver::Param] = &[
+        solver::Param { name: "size", help: "width and height of the room", default: solver::Value::Pair(SIZE.0, SIZE.1) },
+    ];
+
+    fn set(&mut self, name: &str, value: solver::Value) -> anyhow::Result<()> {
+        match name {
+            "size" => self.1 = value.pair::<i64>()?.into(),
+            _ => return solver::unknown_param(name),
+        }
+        Ok(())
+    }
+
+    fn part_one(self: &Self) -> impl solver::Answer {
+        let Pt(xmax, ymax) = self.1;
+        let pos = self.0.iter().map(|&(pos, speed)| pos.torus_add(speed * 100, self.1)).collect::<Vec<_>>();
         let (xmid, ymid) = ((xmax-1)/2, (ymax-1)/2);
-        pos.iter().filter(|&&(x, y)| x < xmid && y < ymid).count() *
-            pos.iter().filter(|&&(x, y)| x < xmid && y > ymid).count() *
-            pos.iter().filter(|&&(x, y)| x > xmid && y < ymid).count() *
-            pos.iter().filter(|&&(x, y)| x > xmid && y > ymid).count()
+        pos.iter().filter(|&&Pt(x, y)| x < xmid && y < ymid).count() *
+            pos.iter().filter(|&&Pt(x, y)| x < xmid && y > ymid).count() *
+            pos.iter().filter(|&&Pt(x, y)| x > xmid && y < ymid).count() *
+            pos.iter().filter(|&&Pt(x, y)| x > xmid && y > ymid).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        let (xmax, ymax) = self.1;
-        for i in 1_000..10_0000 {
-            let pos = self.0.iter().map(|&((xpos, ypos), (xs, ys))| {
-                ((xpos + i*xs).rem_euclid(xmax), (ypos + i*ys).rem_euclid(ymax))
-            }).collect::<Vec<_>>();
-            if let Some((y, count)) = pos.iter().map(|&(_, y)| y)
+    fn part_two(self: &Self) -> impl solver::Answer {
+        let Pt(xmax, ymax) = self.1;
+        let token = cancel::token();
+        for i in (1_000..10_0000).take_while(|_| !token.is_cancelled()) {
+            let pos = self.0.iter().map(|&(pos, speed)| pos.torus_add(speed * i, self.1)).collect::<Vec<_>>();
+            if let Some((y, count)) = pos.iter().map(|&Pt(_, y)| y)
                 .fold(HashMap::<i64, usize>::new(), |mut m, x| {
                     *m.entry(x).or_default() += 1;
                     m
@@ -49,58 +61,50 @@ impl solver::Solver for Problem {
                 .into_iter()
                 .max_by_key(|(_, v)| *v) {
                     if count > 10 {
-                        let vals = &mut pos.iter().filter(|&&p| p.1 == y).map(|(x, _)| *x).collect::<Vec<_>>();
+                        let vals = &mut pos.iter().filter(|&&p| p.1 == y).map(|p| p.0).collect::<Vec<_>>();
                         vals.sort();
                         vals.dedup();
                         if vals.len() >= 10 {
                             for l in 0..(vals.len()-10) {
                                 if vals[l]+10 == vals[l+10] {
-                                    // PRINT TO CHECK IF THE TREE IS THERE
-                                    // let okok = &mut vec![vec!['.'; xmax.try_into().unwrap()]; ymax.try_into().unwrap()];
-                                    // pos.iter().for_each(|&(x, y)| {
-                                    //     let iop = &mut okok[TryInto::<usize>::try_into(y).unwrap()];
-                                    //     iop[TryInto::<usize>::try_into(x).unwrap()] = 'X';
-                                    // });
-                                    // println!("row: {}", y);
-                                    // okok.iter().for_each(|row| {
-                                    //     row.iter().for_each(|c| {
-                                    //         print!("{}", c);
-                                    //     });
-                                    //     print!("{}", '\n');
-                                    // });
-                                    return i;
+                                    // shows the room to check that the tree is there
+                                    trace::debug!("row {} after {} seconds:\n{}", y, i, (0..ymax)
+                                        .map(|y| (0..xmax).map(|x| if pos.contains(&Pt(x, y)) { 'X' } else { '.' }).collect::<String>())
+                                        .collect::<Vec<_>>()
+                                        .join("\n"));
+                                    return solver::Fallible(Ok(i));
                                 }
                             }
                         }
                     }
                 }
         }
-        0
+        solver::Fallible(Err(token.failure()))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("12", format!("{}", Problem(vec![
-            ((0,4), (3,-3)),
-            ((6,3), (-1,-3)),
-            ((10,3), (-1,2)),
-            ((2,0), (2,-1)),
-            ((0,0), (1,3)),
-            ((3,0), (-2,-2)),
-            ((7,6), (-1,-3)),
-            ((3,0), (-1,-2)),
-            ((9,3), (2,3)),
-            ((7,3), (-1,2)),
-            ((2,4), (2,-3)),
-            ((9,5), (-3,-3)),
-        ], (11, 7)).part_one()));
+        assert_eq!(Ok("12".to_string()), Problem(vec![
+            (Pt(0,4), Pt(3,-3)),
+            (Pt(6,3), Pt(-1,-3)),
+            (Pt(10,3), Pt(-1,2)),
+            (Pt(2,0), Pt(2,-1)),
+            (Pt(0,0), Pt(1,3)),
+            (Pt(3,0), Pt(-2,-2)),
+            (Pt(7,6), Pt(-1,-3)),
+            (Pt(3,0), Pt(-1,-2)),
+            (Pt(9,3), Pt(2,3)),
+            (Pt(7,3), Pt(-1,2)),
+            (Pt(2,4), Pt(2,-3)),
+            (Pt(9,5), Pt(-3,-3)),
+        ], Pt(11, 7)).part_one().answer());
     }
 
     #[test]
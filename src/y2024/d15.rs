@@ -1,11 +1,13 @@
-use std::{collections::{HashSet, VecDeque}, io::{BufRead, Lines}, ops::{Add, Sub}};
-use crate::solver;
+use std::{collections::{HashSet, VecDeque}, fmt::Display, io::{BufRead, Lines}};
+use crate::{map::{Direction, Grid, Pt}, parse, solver, trace};
 use anyhow::Result;
 use std::hash::Hash;
 
 trait MapPiece {
     fn is_movable(self) -> bool;
     fn positions(self) -> impl Iterator<Item = (isize, isize)>;
+    /// Character of the piece in the puzzle description.
+    fn symbol(self) -> char;
 }
 
 #[derive(PartialEq, Copy, Clone)]
@@ -27,27 +29,12 @@ impl MapPiece for Element {
     fn positions(self) -> impl Iterator<Item = (isize, isize)> {
         [(0, 0)].into_iter()
     }
-}
-
-#[derive(PartialEq, Copy, Clone, Debug)]
-enum Move {
-    Up,
-    Right,
-    Down,
-    Left,
-}
 
-impl<T> Add<Move> for (T, T)
-where T: Sub<usize, Output = T> + Add<usize, Output = T>
-{
-    type Output = Self;
-
-    fn add(self, rhs: Move) -> Self::Output {
-        match rhs {
-            Move::Up => (self.0 - 1, self.1),
-            Move::Right => (self.0, self.1 + 1),
-            Move::Down => (self.0 + 1, self.1),
-            Move::Left => (self.0, self.1 - 1),
+    fn symbol(self) -> char {
+        match self {
+            Element::Wall => '#',
+            Element::Box => 'O',
+            Element::Robot => '@',
         }
     }
 }
@@ -72,28 +59,45 @@ impl MapPiece for ScaledElement {
     fn positions(self) -> impl Iterator<Item = (isize, isize)> {
         match self {
             ScaledElement::Wall | ScaledElement::Robot => vec![(0, 0)].into_iter(),
-            ScaledElement::BoxLeft => vec![(0, 0), (0, 1)].into_iter(),
-            ScaledElement::BoxRight => vec![(0, -1), (0, 0)].into_iter(),
+            ScaledElement::BoxLeft => vec![(0, 0), (1, 0)].into_iter(),
+            ScaledElement::BoxRight => vec![(-1, 0), (0, 0)].into_iter(),
         }
         
     }
+
+    fn symbol(self) -> char {
+        match self {
+            ScaledElement::Wall => '#',
+            ScaledElement::BoxLeft => '[',
+            ScaledElement::BoxRight => ']',
+            ScaledElement::Robot => '@',
+        }
+    }
 }
 
 struct Map<T: PartialEq> {
-    grid: Vec<Vec<Option<T>>>,
-    pos: (usize, usize),
+    grid: Grid<Option<T>>,
+    pos: Pt<usize>,
+}
+
+impl<T: PartialEq + Copy + MapPiece> Display for Map<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.rows().try_for_each(|row| {
+            writeln!(f, "{}", row.iter().map(|e| e.map_or('.', |e| e.symbol())).collect::<String>())
+        })
+    }
 }
 
 impl From<Map<Element>> for Map<ScaledElement> {
-    fn from(Map { grid, pos: (x, y) }: Map<Element>) -> Self {
+    fn from(Map { grid, pos: Pt(x, y) }: Map<Element>) -> Self {
         Self {
-            grid: grid.into_iter().map(|row| row.into_iter().flat_map(|e| match e {
+            grid: grid.rows().map(|row| row.iter().flat_map(|e| match e {
                 None => [None, None],
                 Some(Element::Wall) => [Some(ScaledElement::Wall), Some(ScaledElement::Wall)],
                 Some(Element::Box) => [Some(ScaledElement::BoxLeft), Some(ScaledElement::BoxRight)],
                 Some(Element::Robot) => [Some(ScaledElement::Robot), None],
-            }).collect()).collect(),
-            pos: (x, y << 1),
+            })).collect(),
+            pos: Pt(x << 1, y),
         }
     }
 }
@@ -134,52 +138,44 @@ impl<N: Hash + Eq + Copy, G: Graph<Item = N>> Iterator for BFSIterator<N, G> {
 }
 
 impl<T: PartialEq + Copy + MapPiece> Map<T> {
-    fn movable(&self, dir: Move) -> Vec<(usize, usize)> {
+    fn movable(&self, dir: Direction) -> Vec<Pt<usize>> {
         let mut ok = BFSIterator::new(self.pos, (&self.grid, dir))
-            .map(|(x, y)| if self.grid[x][y].is_some_and(|l| !l.is_movable()) { Err(FixedItemError) } else { Ok((x, y)) })
+            .map(|pt| if self.grid[pt].is_some_and(|l| !l.is_movable()) { Err(FixedItemError) } else { Ok(pt) })
             .collect::<Result<Vec<_>, _>>().unwrap_or_else(|_| vec![]);
-        ok.sort_by_key(|&(x, y)| -> isize {
-            let v: isize = match dir {
-                Move::Up | Move::Down => x.try_into().unwrap(),
-                Move::Right | Move::Left => y.try_into().unwrap(),
-            };
-            v * (match dir {
-                Move::Up | Move::Left => 1_isize,
-                Move::Right | Move::Down => -1,
-            })
-        });
+        // Pieces furthest in the direction of the move go first, to make room for the others.
+        let (dx, dy) = dir.delta();
+        ok.sort_by_key(|&Pt(x, y)| -(x as isize * dx + y as isize * dy));
         ok
 
     }
 
-    fn step(&mut self, dir: Move) {
+    fn step(&mut self, dir: Direction) {
         let i = self.movable(dir);
         if !i.is_empty() {
             self.pos = self.pos + dir
         }
-        for (x, y) in i {
-            let (next_x, next_y) = (x, y) + dir;
-            self.grid[next_x][next_y] = self.grid[x][y];
-            self.grid[x][y] = None;
+        for pt in i {
+            self.grid[pt + dir] = self.grid[pt];
+            self.grid[pt] = None;
         }
     }
 }
 
 struct FixedItemError;
 
-impl<T: Copy + PartialEq + MapPiece> Graph for (&Vec<Vec<Option<T>>>, Move) {
-    type Item = (usize, usize);
+impl<T: Copy + PartialEq + MapPiece> Graph for (&Grid<Option<T>>, Direction) {
+    type Item = Pt<usize>;
 
-    fn next(&self, (x, y): Self::Item) -> impl Iterator<Item = Self::Item> {
+    fn next(&self, pt: Self::Item) -> impl Iterator<Item = Self::Item> {
         let &(map, dir) = self;
-        if map[x][y].as_ref().is_none_or(|l| !l.is_movable()) {
+        if map[pt].as_ref().is_none_or(|l| !l.is_movable()) {
             return vec![].into_iter();
         }
 
-        let next = (x, y) + dir;
-        if let Some(el) = map[next.0][next.1] {
+        let next = pt + dir;
+        if let Some(el) = map[next] {
             el.positions()
-                .map(|(dx, dy)| (next.0.saturating_add_signed(dx), next.1.saturating_add_signed(dy)))
+                .map(|(dx, dy)| Pt(next.0.saturating_add_signed(dx), next.1.saturating_add_signed(dy)))
                 .collect::<Vec<_>>().into_iter()
         } else {
             vec![].into_iter()
@@ -187,121 +183,71 @@ impl<T: Copy + PartialEq + MapPiece> Graph for (&Vec<Vec<Option<T>>>, Move) {
     }
 }
 
-pub struct Problem(Vec<Vec<Option<Element>>>, Vec<Move>);
+pub struct Problem(Grid<Option<Element>>, Pt<usize>, Vec<Direction>);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let mut lines = value;
-        let a = lines.by_ref().take_while(|p| p.as_ref().is_ok_and(|s| !s.is_empty()))
-            .map(|l| {
-                let l = l?;
-                anyhow::Ok(l.chars().map(|l| Ok(match l {
-                    '#' => Some(Element::Wall),
-                    '.' => None,
-                    'O' => Some(Element::Box),
-                    '@' => Some(Element::Robot),
-                    _ => anyhow::bail!("unknown char"),
-                })).collect::<Result<Vec<_>, _>>())?
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        let b = lines
-            .map(|l| {
-                let l = l?;
-                anyhow::Ok(l.chars().map(|l| Ok(match l {
-                    '^' => Move::Up,
-                    '>' => Move::Right,
-                    'v' => Move::Down,
-                    '<' => Move::Left,
-                    _ => anyhow::bail!("unknown char"),
-                })).collect::<Result<Vec<_>, _>>())?
-            })
+        let text = parse::Text::read(value)?;
+        let [map, moves] = &text.sections()[..] else {
+            anyhow::bail!("expected the map and the moves separated by a blank line");
+        };
+        let (grid, [robot]) = Grid::parse(map.iter().copied(), ['@'], |c| match c {
+            '#' => Some(Some(Element::Wall)),
+            '.' => Some(None),
+            'O' => Some(Some(Element::Box)),
+            '@' => Some(Some(Element::Robot)),
+            _ => None,
+        })?;
+        let moves = moves.iter()
+            .map(|l| l.chars(|c| match c {
+                '^' | '>' | 'v' | '<' => Direction::try_from(c).ok(),
+                _ => None,
+            }))
             .collect::<Result<Vec<_>, _>>()?
             .into_iter().flatten().collect::<Vec<_>>();
-        Ok(Self(a, b))
+        Ok(Self(grid, robot, moves))
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        let Some(pos) = self.0.iter().enumerate().find_map(|(y, row)| row.iter().enumerate().find_map(|(x, c)| if *c == Some(Element::Robot) { Some((x, y)) } else { None })) else {
-            panic!("unable to find robot");
-        };
-
+    fn part_one(self: &Self) -> impl solver::Answer {
         let mut map = Map {
             grid: self.0.clone(),
-            pos,
+            pos: self.1,
         };
-        self.1.iter().for_each(|&m| {
+        self.2.iter().for_each(|&m| {
             map.step(m);
-            // println!("{:?}", m);
-            // for i in 0..8 {
-            //     for j in 0..8 {
-            //         print!("{}", match map.grid[i][j] {
-            //             None => '.',
-            //             Some(Element::Wall) => '#',
-            //             Some(Element::Box) => 'O',
-            //             Some(Element::Robot) => '@',
-            //         });
-            //     }
-            //     print!("\n");
-            // }
+            trace::trace!("move {:?}:\n{}", m, map);
         });
-        map.grid.iter().enumerate().flat_map(|(y, el)| el.iter().enumerate().filter_map(move |(x, el)| if el == &Some(Element::Box) { Some(x + 100*y) } else { None })).sum::<usize>()
+        map.grid.all_positions().filter(|&pt| map.grid[pt] == Some(Element::Box)).map(|Pt(x, y)| x + 100*y).sum::<usize>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        let Some(pos) = self.0.iter().enumerate().find_map(|(x, row)| row.iter().enumerate().find_map(|(y, c)| if *c == Some(Element::Robot) { Some((x, y)) } else { None })) else {
-            panic!("unable to find robot");
-        };
-
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut map: Map<ScaledElement> = Map {
             grid: self.0.clone(),
-            pos,
+            pos: self.1,
         }.into();
-        // for i in 0..7 {
-        //     for j in 0..14 {
-        //         print!("{}", match map.grid[i][j] {
-        //             None => '.',
-        //             Some(ScaledElement::Wall) => '#',
-        //             Some(ScaledElement::BoxLeft) => '[',
-        //             Some(ScaledElement::BoxRight) => ']',
-        //             Some(ScaledElement::Robot) => '@',
-        //         });
-        //     }
-        //     print!("\n");
-        // }
-
-        self.1.iter().for_each(|&m| {
+        trace::trace!("scaled map:\n{}", map);
+
+        self.2.iter().for_each(|&m| {
             map.step(m);
-            // println!("{:?}", m);
-            // for i in 0..7 {
-            //     for j in 0..14 {
-            //         print!("{}", match map.grid[i][j] {
-            //             None => '.',
-            //             Some(ScaledElement::Wall) => '#',
-            //             Some(ScaledElement::BoxLeft) => '[',
-            //             Some(ScaledElement::BoxRight) => ']',
-            //             Some(ScaledElement::Robot) => '@',
-            //         });
-            //     }
-            //     print!("\n");
-            // }
+            trace::trace!("move {:?}:\n{}", m, map);
         });
-        map.grid.iter().enumerate().flat_map(|(y, el)| el.iter().enumerate().filter_map(move |(x, el)| if el == &Some(ScaledElement::BoxLeft) { Some(x + 100*y) } else { None })).sum::<usize>()
+        map.grid.all_positions().filter(|&pt| map.grid[pt] == Some(ScaledElement::BoxLeft)).map(|Pt(x, y)| x + 100*y).sum::<usize>()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("2028", format!("{}", Problem(vec![
+        assert_eq!(Ok("2028".to_string()), Problem(vec![
             vec![Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall)],
             vec![Some(Element::Wall), None, None, Some(Element::Box), None, Some(Element::Box), None, Some(Element::Wall)],
             vec![Some(Element::Wall), Some(Element::Wall), Some(Element::Robot), None, Some(Element::Box), None, None, Some(Element::Wall)],
@@ -310,10 +256,10 @@ mod tests {
             vec![Some(Element::Wall), None, None, None, Some(Element::Box), None, None, Some(Element::Wall)],
             vec![Some(Element::Wall), None, None, None, None, None, None, Some(Element::Wall)],
             vec![Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall), Some(Element::Wall)],
-        ], vec![
-            Move::Left, Move::Up, Move::Up, Move::Right, Move::Right, Move::Right, Move::Down, Move::Down, Move::Left, Move::Down, Move::Right,
-            Move::Right, Move::Down, Move::Left, Move::Left,
-        ]).part_one()));
+        ].into_iter().collect(), Pt(2, 2), vec![
+            Direction::W, Direction::N, Direction::N, Direction::E, Direction::E, Direction::E, Direction::S, Direction::S, Direction::W, Direction::S, Direction::E,
+            Direction::E, Direction::S, Direction::W, Direction::W,
+        ]).part_one().answer());
     }
 
     #[test]
@@ -339,7 +285,14 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^".as_bytes().lines().try_into()?;
-        assert_eq!("9021", format!("{}", ex.part_two()));
+        assert_eq!("9021", ex.part_two().answer()?);
         Ok(())
     }
+
+    #[test]
+    fn moves() {
+        let pb = |moves: &str| Problem::try_from(format!("###\n#@#\n###\n\n{}", moves).as_bytes().lines());
+        assert!(pb("<^v>").is_ok_and(|pb| pb.2 == [Direction::W, Direction::N, Direction::S, Direction::E]));
+        assert!(pb("<U>").is_err());
+    }
 }
@@ -1,5 +1,5 @@
 use std::io::{BufRead, Lines};
-use crate::solver;
+use crate::{solver, trace};
 use anyhow::{anyhow, bail, ensure, Result};
 
 #[derive(Clone, Copy, Debug)]
@@ -12,7 +12,7 @@ struct Machine {
 }
 
 trait Operand {
-    fn value(self, machine: &Machine) -> u64;
+    fn value(self, machine: &Machine) -> Result<u64, solver::Error>;
 }
 
 #[derive(Debug)]
@@ -28,8 +28,8 @@ impl TryFrom<u8> for LiteralOperand {
 }
 
 impl Operand for LiteralOperand {
-    fn value(self, _machine: &Machine) -> u64 {
-        self.0.into()
+    fn value(self, _machine: &Machine) -> Result<u64, solver::Error> {
+        Ok(self.0.into())
     }
 }
 
@@ -58,14 +58,14 @@ impl TryFrom<u8> for ComboOperand {
 }
 
 impl Operand for ComboOperand {
-    fn value(self, machine: &Machine) -> u64 {
-        match self {
+    fn value(self, machine: &Machine) -> Result<u64, solver::Error> {
+        Ok(match self {
             Self::Literal(v) => v.into(),
             Self::RegA => machine.a,
             Self::RegB => machine.b,
             Self::RegC => machine.c,
-            Self::Invalid => panic!("using reserved combo operand"),
-        }
+            Self::Invalid => return Err(solver::Error::InvalidInput(format!("reserved combo operand used at {}", machine.ptr))),
+        })
     }
 }
 
@@ -82,26 +82,34 @@ enum Instruction {
 }
 
 impl Instruction {
-    fn process(self: Self, state: &mut Machine) -> Option<u64> {
+    fn process(self: Self, state: &mut Machine) -> Result<Option<u64>, solver::Error> {
         match self {
-            Self::Adv(operand) => state.a >>= operand.value(state),
-            Self::Bxl(operand) => state.b ^= operand.value(state),
-            Self::Bst(operand) => state.b = operand.value(state) & 7,
+            Self::Adv(operand) => state.a >>= operand.value(state)?,
+            Self::Bxl(operand) => state.b ^= operand.value(state)?,
+            Self::Bst(operand) => state.b = operand.value(state)? & 7,
             Self::Jnz(operand) => if state.a != 0 {
-                state.ptr = operand.value(state).try_into().unwrap();
-                return None;
+                state.ptr = operand.value(state)? as usize;
+                return Ok(None);
             },
             Self::Bxc => state.b ^= state.c,
             Self::Out(operand) => {
-                let res = operand.value(state) & 7;
+                let res = operand.value(state)? & 7;
                 state.ptr += 2;
-                return Some(res);
+                return Ok(Some(res));
             },
-            Self::Bdv(operand) => state.b = state.a >> operand.value(state),
-            Self::Cdv(operand) => state.c = state.a >> operand.value(state),
+            Self::Bdv(operand) => state.b = state.a >> operand.value(state)?,
+            Self::Cdv(operand) => state.c = state.a >> operand.value(state)?,
         };
         state.ptr += 2;
-        None
+        Ok(None)
+    }
+
+    /// Decodes the instruction pointed by the machine, if any.
+    fn fetch(state: &Machine, prog: &[u8]) -> Result<Option<Self>, solver::Error> {
+        let Some(&[opcode, operand]) = prog.get(state.ptr..state.ptr+2) else {
+            return Ok(None);
+        };
+        (opcode, operand).try_into().map(Some).map_err(|e: anyhow::Error| solver::Error::InvalidInput(format!("{} at {}", e, state.ptr)))
     }
 }
 
@@ -142,60 +150,61 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let mut res = vec![];
         let state = &mut self.0.clone();
 
-        while state.ptr < self.1.len() {
-            let instruct: Instruction = (self.1[state.ptr], self.1[state.ptr+1]).try_into().unwrap();
-            instruct.process(state).iter().for_each(|&v| { res.push(v); });
-        }
-
-        res.iter().map(|&v| format!("{}", v)).collect::<Vec<_>>().join(",")
+        solver::Fallible((|| {
+            while let Some(instruct) = Instruction::fetch(state, &self.1)? {
+                instruct.process(state)?.iter().for_each(|&v| { res.push(v); });
+            }
+            Ok(res.iter().map(|&v| format!("{}", v)).collect::<Vec<_>>().join(","))
+        })())
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        fn run(machine: &Machine, expected: &[u64], prog: &[u8], a: u64) -> Option<u64> {
+    fn part_two(self: &Self) -> impl solver::Answer {
+        fn run(machine: &Machine, expected: &[u64], prog: &[u8], a: u64) -> Result<Option<u64>, solver::Error> {
             for i in a..(a+16) {
                 let mut res = vec![];
                 let state = &mut machine.clone();
                 state.a = i;
         
-                while state.ptr < prog.len() {
-                    let instruct: Instruction = (prog[state.ptr], prog[state.ptr+1]).try_into().unwrap();
-                    instruct.process(state).iter().for_each(|&v| { res.push(v); });
+                while let Some(instruct) = Instruction::fetch(state, prog)? {
+                    instruct.process(state)?.iter().for_each(|&v| { res.push(v); });
                 }
 
-                // println!("{} {}", i, &res.iter().map(|l| format!("{}", l)).collect::<Vec<_>>().join(","));
-                if res[..] == expected[(expected.len()-res.len())..] {
+                trace::trace!("a={} outputs {}", i, res.iter().map(|l| format!("{}", l)).collect::<Vec<_>>().join(","));
+                if res.len() <= expected.len() && res[..] == expected[(expected.len()-res.len())..] {
                     if res.len() == expected.len() {
-                        return Some(i);
+                        return Ok(Some(i));
                     }
-                    if let Some(r) = run(machine, expected, prog, i*8) {
-                        return Some(r);
+                    if let Some(r) = run(machine, expected, prog, i*8)? {
+                        return Ok(Some(r));
                     }
                 }
             }
-            None
+            Ok(None)
         }
 
-        run(&self.0.clone(), &self.1.iter().map(|&l| l as u64).collect::<Vec<_>>(), &self.1, 1).unwrap_or_default()
+        solver::Fallible(run(&self.0.clone(), &self.1.iter().map(|&l| l as u64).collect::<Vec<_>>(), &self.1, 1)
+            .and_then(|a| a.ok_or(solver::Error::NoSolution)))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("4,6,3,5,6,3,5,2,1,0", format!("{}", Problem(Machine { a: 729, b: 0, c: 0, ptr: 0 }, vec![0,1,5,4,3,0]).part_one()));
+        assert_eq!(Ok("4,6,3,5,6,3,5,2,1,0".to_string()), Problem(Machine { a: 729, b: 0, c: 0, ptr: 0 }, vec![0,1,5,4,3,0]).part_one().answer());
+        assert!(matches!(Problem(Machine { a: 729, b: 0, c: 0, ptr: 0 }, vec![5,7]).part_one().answer(), Err(solver::Error::InvalidInput(_))));
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("117440", format!("{}", Problem(Machine { a: 2024, b: 0, c: 0, ptr: 0 }, vec![0,3,5,4,3,0]).part_two()));
+        assert_eq!(Ok("117440".to_string()), Problem(Machine { a: 2024, b: 0, c: 0, ptr: 0 }, vec![0,3,5,4,3,0]).part_two().answer());
     }
 }
@@ -1,63 +1,70 @@
-use std::{collections::HashSet, io::{BufRead, Lines}};
-use crate::solver;
-use anyhow::{anyhow, Result};
+use std::io::{BufRead, Lines};
+use crate::{map::{Grid, Pt}, parse, solver, trace};
+use anyhow::Result;
 use pathfinding::directed::astar;
 
+const SIZE: (usize, usize) = (71, 71);
+const FALLEN: usize = 1024;
+
 pub struct Problem(Vec<(usize, usize)>, (usize, usize), usize);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let a = value
+        let text = parse::Text::read(value)?;
+        let a = text.lines()
             .map(|l| {
-                let l = l?;
-                let (a, b) = l.split_once(",").ok_or_else(|| anyhow!("missing comma"))?;
-                anyhow::Ok((a.parse::<usize>()?, b.parse::<usize>()?))
-            }).collect::<Result<Vec<_>, _>>()?;
+                let (a, b) = l.split_once(",")?;
+                Ok((a.parse::<usize>()?, b.parse::<usize>()?))
+            }).collect::<Result<Vec<_>, parse::Error>>()?;
 
-        Ok(Self(a, (71, 71), 1024))
+        Ok(Self(a, SIZE, FALLEN))
     }
 }
 
 impl Problem {
     fn find_path(self: &Self, count: usize) -> Option<usize> {
-        let bytes = &HashSet::<_>::from_iter(self.0.iter().take(count).copied());
-        let Some((path, _)) = astar::astar(&(0, 0), |&(x, y)| {
-            let mut res = vec![];
-            if x > 0 && !bytes.contains(&(x-1, y)) {
-                res.push(((x-1, y), 1));
-            }
-            if y > 0 && !bytes.contains(&(x, y-1)) {
-                res.push(((x, y-1), 1));
-            }
-            if x < self.1.0-1 && !bytes.contains(&(x+1, y)) {
-                res.push(((x+1, y), 1));
-            }
-            if y < self.1.1-1 && !bytes.contains(&(x, y+1)) {
-                res.push(((x, y+1), 1));
-            }
-            res
-        }, |&(x, y)| (self.1.0-1).abs_diff(x) + (self.1.1-1).abs_diff(y), |&(x, y)| (self.1.0-1) == x && (self.1.1-1) == y) else {
-            return None;
-        };
-
-        // for j in 0..self.1.1 {
-        //     for i in 0..self.1.0 {
-        //         print!("{}", if bytes.contains(&(i,j)) { "#" } else { "." });
-        //     }
-        //     print!("\n");
-        // }
+        let mut bytes = Grid::new_dyn(self.1.0, self.1.1, false);
+        // Bytes beyond a memory space made smaller with `--param size` fall outside of it.
+        self.0.iter().take(count).filter(|&&(x, y)| x < self.1.0 && y < self.1.1).for_each(|&pt| bytes[pt.into()] = true);
+        let end = Pt(self.1.0-1, self.1.1-1);
+        let (path, _) = astar::astar(&Pt(0, 0), |&pt| {
+            bytes.orthogonal_neighbours(pt).filter(|&(next, _)| !bytes[next]).map(|(next, _)| (next, 1)).collect::<Vec<_>>()
+        }, |&pt| pt.manhattan_distance(end), |&pt| pt == end)?;
+
+        trace::trace!("memory space after {} bytes:\n{}", count, bytes.rows()
+            .map(|row| row.iter().map(|&b| if b { '#' } else { '.' }).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n"));
         Some(path.len()-1)
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        self.find_path(self.2).unwrap_or_default()
+    const PARAMS: &'static [solver::Param] = &[
+        solver::Param { name: "size", help: "width and height of the memory space", default: solver::Value::Pair(SIZE.0 as i64, SIZE.1 as i64) },
+        solver::Param { name: "bytes", help: "fallen bytes of part one", default: solver::Value::Int(FALLEN as i64) },
+    ];
+
+    fn set(&mut self, name: &str, value: solver::Value) -> anyhow::Result<()> {
+        match name {
+            "size" => self.1 = value.pair()?,
+            "bytes" => self.2 = value.int()?,
+            _ => return solver::unknown_param(name),
+        }
+        Ok(())
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
+        solver::Fallible(self.find_path(self.2).ok_or(solver::Error::NoSolution))
+    }
+
+    fn part_two(self: &Self) -> impl solver::Answer {
+        // Otherwise the search below would never find a byte cutting the path.
+        if self.find_path(self.0.len()).is_some() {
+            return solver::Fallible(Err(solver::Error::NoSolution));
+        }
         let mut i = self.2;
         while self.find_path(i).is_some() {
             i <<= 1;
@@ -73,19 +80,19 @@ impl solver::Solver for Problem {
             }
         }
 
-        format!("{},{}", self.0[i].0, self.0[i].1)
+        solver::Fallible(Ok(format!("{},{}", self.0[i].0, self.0[i].1)))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("22", format!("{}", Problem(vec![
+        assert_eq!(Ok("22".to_string()), Problem(vec![
             (5,4),
             (4,2),
             (4,5),
@@ -111,12 +118,12 @@ mod tests {
             (0,5),
             (1,6),
             (2,0),
-        ], (7, 7), 12).part_one()));
+        ], (7, 7), 12).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("6,1", format!("{}", Problem(vec![
+        assert_eq!(Ok("6,1".to_string()), Problem(vec![
             (5,4),
             (4,2),
             (4,5),
@@ -142,6 +149,18 @@ mod tests {
             (0,5),
             (1,6),
             (2,0),
-        ], (7, 7), 12).part_two()));
+        ], (7, 7), 12).part_two().answer());
+    }
+
+    #[test]
+    fn outside_bytes() {
+        assert_eq!(Ok("12".to_string()), Problem(vec![(1, 0), (70, 70), (0, 1)], (7, 7), 2).part_one().answer());
+    }
+
+    #[test]
+    fn blocked() {
+        let pb = Problem(vec![(1, 0), (0, 1)], (7, 7), 2);
+        assert_eq!(Err(solver::Error::NoSolution), pb.part_one().answer());
+        assert_eq!(Err(solver::Error::NoSolution), Problem(vec![(1, 0)], (7, 7), 1).part_two().answer());
     }
 }
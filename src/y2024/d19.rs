@@ -1,6 +1,6 @@
 use std::{collections::HashMap, io::{BufRead, Lines}};
-use crate::solver;
-use anyhow::{anyhow, Result};
+use crate::{parse, solver};
+use anyhow::{bail, Result};
 
 pub struct Problem(Vec<String>, Vec<String>);
 
@@ -8,11 +8,14 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let mut a = value;
-        let patterns = a.next().ok_or_else(|| anyhow!("expected available patterns"))??.split(", ").map(|s| s.to_owned()).collect::<Vec<_>>();
-        a.next();
-        let req = a.collect::<Result<Vec<_>, _>>()?;
-        Ok(Self(patterns, req))
+        let text = parse::Text::read(value)?;
+        let sections = text.sections();
+        let [patterns, designs] = sections.as_slice() else {
+            bail!("expected available patterns and designs separated by a blank line");
+        };
+        let patterns = patterns.iter().flat_map(|l| l.split(", ")).map(|s| s.as_str().to_owned()).collect::<Vec<_>>();
+        let designs = designs.iter().map(|l| l.as_str().to_owned()).collect::<Vec<_>>();
+        Ok(Self(patterns, designs))
     }
 }
 
@@ -55,14 +58,14 @@ impl Problem {
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    fn part_one(self: &Self) -> impl solver::Answer {
         let cache = &mut HashMap::<&str, bool>::new();
         self.1.iter().filter(|&result| {
             self.can_produce(cache, result)
         }).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let cache = &mut HashMap::<&str, u64>::new();
         self.1.iter().map(|result| {
             self.count_arrangments(cache, result)
@@ -72,13 +75,13 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("6", format!("{}", Problem(vec![
+        assert_eq!(Ok("6".to_string()), Problem(vec![
             "r".to_string(), "wr".to_string(), "b".to_string(), "g".to_string(), "bwu".to_string(), "rb".to_string(), "gb".to_string(), "br".to_string(),
         ], vec![
             "brwrr".to_string(),
@@ -89,12 +92,12 @@ mod tests {
             "bwurrg".to_string(),
             "brgr".to_string(),
             "bbrgwb".to_string(),
-        ]).part_one()));
+        ]).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("16", format!("{}", Problem(vec![
+        assert_eq!(Ok("16".to_string()), Problem(vec![
             "r".to_string(), "wr".to_string(), "b".to_string(), "g".to_string(), "bwu".to_string(), "rb".to_string(), "gb".to_string(), "br".to_string(),
         ], vec![
             "brwrr".to_string(),
@@ -105,6 +108,6 @@ mod tests {
             "bwurrg".to_string(),
             "brgr".to_string(),
             "bbrgwb".to_string(),
-        ]).part_two()));
+        ]).part_two().answer());
     }
 }
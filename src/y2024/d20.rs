@@ -1,14 +1,15 @@
-use std::{collections::{HashMap, HashSet}, io::{BufRead, Lines}, iter};
-use crate::solver;
-use anyhow::{anyhow, Result};
+use std::{collections::HashMap, io::{BufRead, Lines}, iter, sync::OnceLock};
+use crate::{map::{Grid, Pt}, parse, solver};
 
-pub struct Problem(HashSet<(usize, usize)>, (usize, usize), (usize, usize), usize);
+const THRESHOLD: usize = 100;
+
+pub struct Problem(Grid<bool>, Pt<usize>, Pt<usize>, usize, OnceLock<Vec<Pt<usize>>>);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> std::result::Result<Self, Self::Error> {
-        (100, value).try_into()
+        (THRESHOLD, value).try_into()
     }
 }
 
@@ -16,63 +17,58 @@ impl<B: BufRead> TryFrom<(usize, Lines<B>)> for Problem {
     type Error = anyhow::Error;
 
     fn try_from((min_improv, value): (usize, Lines<B>)) -> std::result::Result<Self, Self::Error> {
-        let a = value
-            .enumerate()
-            .flat_map(|(y, row)| row.map_or_else(
-                |e| vec![Err(e)],
-                |row| row.chars().enumerate().map(|(x, c)| Ok((x, y, c))).collect::<Vec<_>>(),
-            ))
-            .collect::<Result<Vec<_>, _>>()?;
-        let start_point = a.iter().find_map(|&(x, y, c)| if c == 'S' { Some((x, y)) } else { None }).ok_or_else(|| anyhow!("unable to find starting point"))?;
-        let end_point = a.iter().find_map(|&(x, y, c)| if c == 'E' { Some((x, y)) } else { None }).ok_or_else(|| anyhow!("unable to find end point"))?;
-        let a = HashSet::from_iter(a.into_iter().filter_map(|(x, y, c)| if c != '#' { Some((x, y)) } else { None }));
-
-        Ok(Self(a, start_point, end_point, min_improv))
+        let (walls, [start_point, end_point]) = Grid::parse(parse::Text::read(value)?.lines(), ['S', 'E'], |c| match c {
+            '#' => Some(true),
+            '.' | 'S' | 'E' => Some(false),
+            _ => None,
+        })?;
+
+        Ok(Self(walls, start_point, end_point, min_improv, OnceLock::new()))
     }
 }
 
-impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        let seq = iter::successors(Some((self.1, self.1)), |&(prev, (x, y))| {
-            if (x, y) == self.2 {
-                return None;
-            }
-            Some(((x, y), *[(x-1, y), (x+1, y), (x, y-1), (x, y+1)].iter().find(|&&next| self.0.contains(&next) && next != prev).unwrap()))
-        }).map(|(_, pos)| pos).collect::<Vec<_>>();
-
-        let track = HashMap::<_, _>::from_iter(seq.into_iter().enumerate().map(|(i, pos)| (pos, i)));
-
-        let cheats = track.iter().flat_map(|(&(x, y), &step)| {
-            let mut reduc = vec![];
-            if !track.contains_key(&(x+1, y)) {
-                if let Some(&next_step) = track.get(&(x+2, y)) {
-                    if next_step > step {
-                        reduc.push(next_step - step - 2);
-                    }
-                }
-            }
-            if !track.contains_key(&(x, y+1)) {
-                if let Some(&next_step) = track.get(&(x, y+2)) {
-                    if next_step > step {
-                        reduc.push(next_step - step - 2);
-                    }
+impl Problem {
+    /// Positions of the race track, from the start to the end.
+    fn track(&self) -> &Vec<Pt<usize>> {
+        self.4.get_or_init(|| {
+            iter::successors(Some((self.1, self.1)), |&(prev, pos)| {
+                if pos == self.2 {
+                    return None;
                 }
-            }
-            if x > 1 && !track.contains_key(&(x-1, y)) {
-                if let Some(&next_step) = track.get(&(x-2, y)) {
-                    if next_step > step {
-                        reduc.push(next_step - step - 2);
-                    }
-                }
-            }
-            if y > 1 && !track.contains_key(&(x, y-1)) {
-                if let Some(&next_step) = track.get(&(x, y-2)) {
-                    if next_step > step {
-                        reduc.push(next_step - step - 2);
-                    }
-                }
-            }
-            reduc
+                let (next, _) = self.0.orthogonal_neighbours(pos).find(|&(next, _)| !self.0[next] && next != prev).unwrap();
+                Some((pos, next))
+            }).map(|(_, pos)| pos).collect()
+        })
+    }
+}
+
+impl solver::Solver for Problem {
+    const PARAMS: &'static [solver::Param] = &[
+        solver::Param { name: "threshold", help: "minimum picoseconds saved by a cheat", default: solver::Value::Int(THRESHOLD as i64) },
+    ];
+
+    fn set(&mut self, name: &str, value: solver::Value) -> anyhow::Result<()> {
+        match name {
+            "threshold" => self.3 = value.int()?,
+            _ => return solver::unknown_param(name),
+        }
+        Ok(())
+    }
+
+    fn prepare(&self) {
+        self.track();
+    }
+
+    fn part_one(self: &Self) -> impl solver::Answer {
+        let track = HashMap::<_, _>::from_iter(self.track().iter().copied().enumerate().map(|(i, pos)| (pos, i)));
+
+        // Cheats go through a wall, to the track right behind it.
+        let cheats = track.iter().flat_map(|(&pos, &step)| {
+            self.0.orthogonal_neighbours(pos)
+                .filter(|(wall, _)| !track.contains_key(wall))
+                .filter_map(|(wall, dir)| self.0.step(wall, dir))
+                .filter_map(|end| track.get(&end).filter(|&&next_step| next_step > step).map(|&next_step| next_step - step - 2))
+                .collect::<Vec<_>>()
         });
         let mut cheat_freqs = HashMap::new();
         cheats.for_each(|v| {
@@ -81,18 +77,13 @@ impl solver::Solver for Problem {
         cheat_freqs.iter().filter_map(|(&reduc, &count)| if reduc >= self.3 { Some(count) } else { None }).sum::<usize>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        let seq = &iter::successors(Some((self.1, self.1)), |&(prev, (x, y))| {
-            if (x, y) == self.2 {
-                return None;
-            }
-            Some(((x, y), *[(x-1, y), (x+1, y), (x, y-1), (x, y+1)].iter().find(|&&next| self.0.contains(&next) && next != prev).unwrap()))
-        }).map(|(_, pos)| pos).collect::<Vec<_>>();
+    fn part_two(self: &Self) -> impl solver::Answer {
+        let seq = self.track();
 
         let shortcuts = seq[..].iter().enumerate().flat_map(|(i, &start)| {
             seq[(i+1)..].iter().enumerate().filter_map(move |(j, &end)| {
                 let j = j+i+1;
-                Some(end.0.abs_diff(start.0) + end.1.abs_diff(start.1))
+                Some(end.manhattan_distance(start))
                     .filter(|&v| v <= 20)
                     .and_then(|v| (j-i).checked_sub(v))
                     .filter(|&v| v >= self.3)
@@ -112,7 +103,7 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
@@ -135,7 +126,7 @@ mod tests {
 #...#...#...###
 ###############";
         let pb: Problem = (12, race_track.as_bytes().lines()).try_into().unwrap();
-        assert_eq!("8", format!("{}", pb.part_one()));
+        assert_eq!(Ok("8".to_string()), pb.part_one().answer());
     }
 
     #[test]
@@ -158,6 +149,6 @@ mod tests {
 ###############";
 
         let pb: Problem = (70, race_track.as_bytes().lines()).try_into().unwrap();
-        assert_eq!("41", format!("{}", pb.part_two()));
+        assert_eq!(Ok("41".to_string()), pb.part_two().answer());
     }
 }
@@ -3,18 +3,32 @@ use crate::solver;
 use anyhow::Result;
 use itertools::Itertools;
 
+const SECRETS: usize = 2000;
+
 pub struct Problem(Vec<i64>, usize);
 
 impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        Ok(Self(value.map(|l| anyhow::Ok(l?.parse::<i64>()?)).collect::<Result<Vec<_>, _>>()?, 2000))
+        Ok(Self(value.map(|l| anyhow::Ok(l?.parse::<i64>()?)).collect::<Result<Vec<_>, _>>()?, SECRETS))
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    const PARAMS: &'static [solver::Param] = &[
+        solver::Param { name: "secrets", help: "new secret numbers generated per buyer", default: solver::Value::Int(SECRETS as i64) },
+    ];
+
+    fn set(&mut self, name: &str, value: solver::Value) -> anyhow::Result<()> {
+        match name {
+            "secrets" => self.1 = value.int()?,
+            _ => return solver::unknown_param(name),
+        }
+        Ok(())
+    }
+
+    fn part_one(self: &Self) -> impl solver::Answer {
         self.0.iter().map(|&v| {
             iter::successors(Some(v), |&v| {
                 let mut v = v;
@@ -29,7 +43,7 @@ impl solver::Solver for Problem {
         }).sum::<i64>()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let mut res = HashMap::new();
         let optim = self.0.iter().map(|&v| {
             let el = iter::successors(Some(v), |&v| {
@@ -41,7 +55,7 @@ impl solver::Solver for Problem {
                 v ^= v << 11;
                 v %= 16777216;
                 Some(v)
-            }).skip(1).take(2000).map(|l| l % 10).collect::<Vec<_>>();
+            }).skip(1).take(self.1).map(|l| l % 10).collect::<Vec<_>>();
             let mut pos = HashMap::new();
             el.into_iter()
                 .tuple_windows::<(_, _)>()
@@ -64,27 +78,27 @@ impl solver::Solver for Problem {
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("37327623", format!("{}", Problem(vec![
+        assert_eq!(Ok("37327623".to_string()), Problem(vec![
             1,
             10,
             100,
             2024,
-        ], 2000).part_one()));
+        ], 2000).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("23", format!("{}", Problem(vec![
+        assert_eq!(Ok("23".to_string()), Problem(vec![
             1,
             2,
             3,
             2024,
-        ], 2000).part_two()));
+        ], 2000).part_two().answer());
     }
 }
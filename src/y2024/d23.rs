@@ -1,161 +1,176 @@
-use std::{collections::{HashMap, HashSet}, io::{BufRead, Lines}, iter};
-use crate::solver;
-use anyhow::{anyhow, Result};
+use std::{collections::{HashMap, HashSet}, iter, sync::OnceLock};
+use crate::{parse, solver};
+use anyhow::Result;
 use itertools::Itertools;
 
-pub struct Problem(Vec<(String, String)>);
+pub struct Problem<'a>(Vec<(&'a str, &'a str)>, OnceLock<Network<'a>>);
 
-impl<B: BufRead> TryFrom<Lines<B>> for Problem {
-    type Error = anyhow::Error;
+impl<'a> solver::FromInput<'a> for Problem<'a> {
+    fn from_input(input: &'a str) -> Result<Self> {
+        Ok(Self(parse::lines(input).map(|l| {
+            let (a, b) = l.split_once("-")?;
+            Ok((a.as_str(), b.as_str()))
+        }).collect::<Result<Vec<_>, parse::Error>>()?, OnceLock::new()))
+    }
+}
+
+/// Computers along with the ones they are directly connected to, both given by their index.
+struct Network<'a> {
+    names: Vec<&'a str>,
+    links: Vec<HashSet<usize>>,
+}
 
-    fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        Ok(Self(value.map(|l| {
-            let l = l?;
-            let (a, b) = l.split_once("-").ok_or_else(|| anyhow!("missing separator"))?;
-            anyhow::Ok((a.to_string(), b.to_string()))
-        }).collect::<Result<Vec<_>, _>>()?))
+impl<'a> Network<'a> {
+    fn new(connections: &[(&'a str, &'a str)]) -> Self {
+        let mut ids = HashMap::<&'a str, usize>::new();
+        let mut links = Vec::<HashSet<usize>>::new();
+        for &(a, b) in connections {
+            let [a, b] = [a, b].map(|name| *ids.entry(name).or_insert_with(|| {
+                links.push(HashSet::new());
+                links.len() - 1
+            }));
+            links[a].insert(b);
+            links[b].insert(a);
+        }
+        let mut names = vec![""; links.len()];
+        ids.into_iter().for_each(|(name, id)| names[id] = name);
+        Self { names, links }
     }
 }
 
-fn bron_kerbosch<'a, F>(r: HashSet<&'a String>, mut p: HashSet<&'a String>, mut x: HashSet<&'a String>, n: &F) -> Vec<HashSet<&'a String>>
-where
-    F: Fn(&'a String) -> &'a HashSet<&'a String>
-{
+fn bron_kerbosch(r: HashSet<usize>, mut p: HashSet<usize>, mut x: HashSet<usize>, links: &[HashSet<usize>]) -> Vec<HashSet<usize>> {
     let mut res = Vec::new();
     if p.is_empty() && x.is_empty() {
         res.extend([r]);
         return res;
     }
     let &u = p.union(&x).next().unwrap();
-    let nu = n(u);
+    let nu = &links[u];
     while let Some(&v) = p.difference(nu).next() {
-        let nv = n(v);
+        let nv = &links[v];
         res.extend(bron_kerbosch(
             r.iter().cloned().chain(iter::once(v)).collect(),
             p.intersection(nv).cloned().collect(),
             x.intersection(nv).cloned().collect(),
-            n,
+            links,
         ));
-        p.remove(v);
+        p.remove(&v);
         x.insert(v);
     }
     res
 }
 
-impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
-        let mut directs = HashMap::<&String, HashSet<&String>>::new();
-        for (a, b) in self.0.iter() {
-            directs.entry(a).and_modify(|s| { s.insert(b); }).or_insert_with(|| HashSet::from([b]));
-            directs.entry(b).and_modify(|s| { s.insert(a); }).or_insert_with(|| HashSet::from([a]));
-        }
+impl<'a> Problem<'a> {
+    fn network(&self) -> &Network<'a> {
+        self.1.get_or_init(|| Network::new(&self.0))
+    }
+}
 
-        let res = HashSet::<_>::from_iter(directs.iter().flat_map(|(&cmp, nexts)|
-            nexts.into_iter()
-                .filter_map(|&next| directs.get(next).map(|s| (next, s)))
-                .flat_map(move |(next, other_nexts)|
-                    other_nexts.intersection(&nexts).map(move |&third| {
-                        let mut els = [cmp, next, third];
-                        els.sort();
-                        els
-                    })
-                )
-        ));
-        res.into_iter().filter(|&els| els.into_iter().any(|el| el.starts_with("t"))).count()
+impl solver::Solver for Problem<'_> {
+    fn prepare(&self) {
+        self.network();
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        let mut directs = HashMap::<&String, HashSet<&String>>::new();
-        for (a, b) in self.0.iter() {
-            directs.entry(a).and_modify(|s| { s.insert(b); }).or_insert_with(|| HashSet::from([b]));
-            directs.entry(b).and_modify(|s| { s.insert(a); }).or_insert_with(|| HashSet::from([a]));
-        }
+    fn part_one(self: &Self) -> impl solver::Answer {
+        let network = self.network();
+        let res = HashSet::<_>::from_iter(network.links.iter().enumerate().flat_map(|(cmp, nexts)|
+            nexts.iter().flat_map(move |&next|
+                network.links[next].intersection(nexts).map(move |&third| {
+                    let mut els = [cmp, next, third];
+                    els.sort();
+                    els
+                })
+            )
+        ));
+        res.into_iter().filter(|els| els.iter().any(|&el| network.names[el].starts_with("t"))).count()
+    }
 
-        let empty = HashSet::new();
-        let res = bron_kerbosch(HashSet::new(), directs.keys().cloned().collect(), HashSet::new(), &|s| directs.get(s).unwrap_or(&empty));
-        res.into_iter().max_by_key(|l| l.len()).unwrap().iter().sorted().join(",")
+    fn part_two(self: &Self) -> impl solver::Answer {
+        let network = self.network();
+        let res = bron_kerbosch(HashSet::new(), (0..network.links.len()).collect(), HashSet::new(), &network.links);
+        res.into_iter().max_by_key(|l| l.len()).unwrap().iter().map(|&id| &network.names[id]).sorted().join(",")
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("7", format!("{}", Problem(vec![
-            ("kh".to_owned(), "tc".to_owned()),
-            ("qp".to_owned(), "kh".to_owned()),
-            ("de".to_owned(), "cg".to_owned()),
-            ("ka".to_owned(), "co".to_owned()),
-            ("yn".to_owned(), "aq".to_owned()),
-            ("qp".to_owned(), "ub".to_owned()),
-            ("cg".to_owned(), "tb".to_owned()),
-            ("vc".to_owned(), "aq".to_owned()),
-            ("tb".to_owned(), "ka".to_owned()),
-            ("wh".to_owned(), "tc".to_owned()),
-            ("yn".to_owned(), "cg".to_owned()),
-            ("kh".to_owned(), "ub".to_owned()),
-            ("ta".to_owned(), "co".to_owned()),
-            ("de".to_owned(), "co".to_owned()),
-            ("tc".to_owned(), "td".to_owned()),
-            ("tb".to_owned(), "wq".to_owned()),
-            ("wh".to_owned(), "td".to_owned()),
-            ("ta".to_owned(), "ka".to_owned()),
-            ("td".to_owned(), "qp".to_owned()),
-            ("aq".to_owned(), "cg".to_owned()),
-            ("wq".to_owned(), "ub".to_owned()),
-            ("ub".to_owned(), "vc".to_owned()),
-            ("de".to_owned(), "ta".to_owned()),
-            ("wq".to_owned(), "aq".to_owned()),
-            ("wq".to_owned(), "vc".to_owned()),
-            ("wh".to_owned(), "yn".to_owned()),
-            ("ka".to_owned(), "de".to_owned()),
-            ("kh".to_owned(), "ta".to_owned()),
-            ("co".to_owned(), "tc".to_owned()),
-            ("wh".to_owned(), "qp".to_owned()),
-            ("tb".to_owned(), "vc".to_owned()),
-            ("td".to_owned(), "yn".to_owned()),
-        ]).part_one()));
+        assert_eq!(Ok("7".to_string()), Problem(vec![
+            ("kh", "tc"),
+            ("qp", "kh"),
+            ("de", "cg"),
+            ("ka", "co"),
+            ("yn", "aq"),
+            ("qp", "ub"),
+            ("cg", "tb"),
+            ("vc", "aq"),
+            ("tb", "ka"),
+            ("wh", "tc"),
+            ("yn", "cg"),
+            ("kh", "ub"),
+            ("ta", "co"),
+            ("de", "co"),
+            ("tc", "td"),
+            ("tb", "wq"),
+            ("wh", "td"),
+            ("ta", "ka"),
+            ("td", "qp"),
+            ("aq", "cg"),
+            ("wq", "ub"),
+            ("ub", "vc"),
+            ("de", "ta"),
+            ("wq", "aq"),
+            ("wq", "vc"),
+            ("wh", "yn"),
+            ("ka", "de"),
+            ("kh", "ta"),
+            ("co", "tc"),
+            ("wh", "qp"),
+            ("tb", "vc"),
+            ("td", "yn"),
+        ], OnceLock::new()).part_one().answer());
     }
 
     #[test]
     fn part_two() {
-        assert_eq!("co,de,ka,ta", format!("{}", Problem(vec![
-            ("kh".to_owned(), "tc".to_owned()),
-            ("qp".to_owned(), "kh".to_owned()),
-            ("de".to_owned(), "cg".to_owned()),
-            ("ka".to_owned(), "co".to_owned()),
-            ("yn".to_owned(), "aq".to_owned()),
-            ("qp".to_owned(), "ub".to_owned()),
-            ("cg".to_owned(), "tb".to_owned()),
-            ("vc".to_owned(), "aq".to_owned()),
-            ("tb".to_owned(), "ka".to_owned()),
-            ("wh".to_owned(), "tc".to_owned()),
-            ("yn".to_owned(), "cg".to_owned()),
-            ("kh".to_owned(), "ub".to_owned()),
-            ("ta".to_owned(), "co".to_owned()),
-            ("de".to_owned(), "co".to_owned()),
-            ("tc".to_owned(), "td".to_owned()),
-            ("tb".to_owned(), "wq".to_owned()),
-            ("wh".to_owned(), "td".to_owned()),
-            ("ta".to_owned(), "ka".to_owned()),
-            ("td".to_owned(), "qp".to_owned()),
-            ("aq".to_owned(), "cg".to_owned()),
-            ("wq".to_owned(), "ub".to_owned()),
-            ("ub".to_owned(), "vc".to_owned()),
-            ("de".to_owned(), "ta".to_owned()),
-            ("wq".to_owned(), "aq".to_owned()),
-            ("wq".to_owned(), "vc".to_owned()),
-            ("wh".to_owned(), "yn".to_owned()),
-            ("ka".to_owned(), "de".to_owned()),
-            ("kh".to_owned(), "ta".to_owned()),
-            ("co".to_owned(), "tc".to_owned()),
-            ("wh".to_owned(), "qp".to_owned()),
-            ("tb".to_owned(), "vc".to_owned()),
-            ("td".to_owned(), "yn".to_owned()),
-        ]).part_two()));
+        assert_eq!(Ok("co,de,ka,ta".to_string()), Problem(vec![
+            ("kh", "tc"),
+            ("qp", "kh"),
+            ("de", "cg"),
+            ("ka", "co"),
+            ("yn", "aq"),
+            ("qp", "ub"),
+            ("cg", "tb"),
+            ("vc", "aq"),
+            ("tb", "ka"),
+            ("wh", "tc"),
+            ("yn", "cg"),
+            ("kh", "ub"),
+            ("ta", "co"),
+            ("de", "co"),
+            ("tc", "td"),
+            ("tb", "wq"),
+            ("wh", "td"),
+            ("ta", "ka"),
+            ("td", "qp"),
+            ("aq", "cg"),
+            ("wq", "ub"),
+            ("ub", "vc"),
+            ("de", "ta"),
+            ("wq", "aq"),
+            ("wq", "vc"),
+            ("wh", "yn"),
+            ("ka", "de"),
+            ("kh", "ta"),
+            ("co", "tc"),
+            ("wh", "qp"),
+            ("tb", "vc"),
+            ("td", "yn"),
+        ], OnceLock::new()).part_two().answer());
     }
 }
@@ -1,70 +1,62 @@
-use std::{collections::HashMap, io::{BufRead, Lines}};
-use crate::solver;
-use anyhow::{anyhow, bail, Result};
-
-#[derive(Clone, Debug)]
-enum Gate {
-    And(String, String),
-    Or(String, String),
-    Xor(String, String),
+use std::collections::HashMap;
+use crate::{parse, solver, trace};
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+#[derive(Clone, Copy, Debug)]
+enum Gate<'a> {
+    And(&'a str, &'a str),
+    Or(&'a str, &'a str),
+    Xor(&'a str, &'a str),
 }
 
-pub struct Problem(HashMap<String, bool>, HashMap<String, Gate>);
+pub struct Problem<'a>(HashMap<&'a str, bool>, HashMap<&'a str, Gate<'a>>);
 
-impl<B: BufRead> TryFrom<Lines<B>> for Problem {
-    type Error = anyhow::Error;
-
-    fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let mut a = value;
-        let init= HashMap::from_iter(a.by_ref()
-            .take_while(|l| {
-                if let Ok(s) = l {
-                    !s.is_empty()
-                } else {
-                    true
-                }
-            })
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
+impl<'a> solver::FromInput<'a> for Problem<'a> {
+    fn from_input(input: &'a str) -> Result<Self> {
+        let sections = parse::sections(input);
+        let [init, ops] = sections.as_slice() else {
+            bail!("expected initial wires and gates separated by a blank line");
+        };
+        let init = HashMap::from_iter(init.iter()
             .map(|s| {
-                let (name, val) = s.split_once(": ").ok_or_else(|| anyhow!("missing colon delim"))?;
-                anyhow::Ok((name.to_owned(), val.parse::<u32>()? == 1))
+                let (name, val) = s.split_once(": ")?;
+                Ok((name.as_str(), val.parse::<u32>()? == 1))
             })
-            .collect::<Result<Vec<_>, _>>()?);
-        let ops = HashMap::from_iter(a.collect::<Result<Vec<_>, _>>()?
-            .iter()
+            .collect::<Result<Vec<_>, parse::Error>>()?);
+        let ops = HashMap::from_iter(ops.iter()
             .map(|s| {
-                let (op, var) = s.split_once(" -> ").ok_or_else(|| anyhow!("missing arrow delim"))?;
-                let op = op.split_whitespace().collect::<Vec<_>>();
-                let (op1, op, op2) = (op[0], op[1], op[2]);
-                anyhow::Ok((var.to_owned(), match op {
-                    "AND" => Gate::And(op1.to_string(), op2.to_string()),
-                    "OR" => Gate::Or(op1.to_string(), op2.to_string()),
-                    "XOR" => Gate::Xor(op1.to_string(), op2.to_string()),
-                    _ => bail!("unknown gate"),
+                let (op, var) = s.split_once(" -> ")?;
+                let [op1, op, op2] = op.fields(" ")?;
+                let (op1, op2) = (op1.as_str(), op2.as_str());
+                Ok((var.as_str(), match op.as_str() {
+                    "AND" => Gate::And(op1, op2),
+                    "OR" => Gate::Or(op1, op2),
+                    "XOR" => Gate::Xor(op1, op2),
+                    _ => return Err(op.error("unknown gate")),
                 }))
             })
-            .collect::<Result<Vec<_>, _>>()?);
+            .collect::<Result<Vec<_>, parse::Error>>()?);
 
         Ok(Self(init, ops))
     }
 }
 
-fn compute(cache: &mut HashMap<String, bool>, ops: &HashMap<String, Gate>, op: String) -> bool {
-    if let Some(&res) = cache.get(&op) {
+fn compute<'a>(cache: &mut HashMap<&'a str, bool>, ops: &HashMap<&'a str, Gate<'a>>, op: &'a str) -> bool {
+    if let Some(&res) = cache.get(op) {
         return res;
     }
-    let gate = ops.get(&op).unwrap();
-    let res = match gate {
-        Gate::And(op1, op2) => compute(cache, ops, op1.clone()) & compute(cache, ops, op2.clone()),
-        Gate::Or(op1, op2) => compute(cache, ops, op1.clone()) | compute(cache, ops, op2.clone()),
-        Gate::Xor(op1, op2) => compute(cache, ops, op1.clone()) ^ compute(cache, ops, op2.clone()),
+    let gate = ops.get(op).unwrap();
+    let res = match *gate {
+        Gate::And(op1, op2) => compute(cache, ops, op1) & compute(cache, ops, op2),
+        Gate::Or(op1, op2) => compute(cache, ops, op1) | compute(cache, ops, op2),
+        Gate::Xor(op1, op2) => compute(cache, ops, op1) ^ compute(cache, ops, op2),
     };
     cache.insert(op, res);
     res
 }
 
-fn build_number(state: &HashMap<String, bool>, name: &str) -> u64 {
+fn build_number(state: &HashMap<&str, bool>, name: &str) -> u64 {
     state.iter()
             .filter(|&(l, _)| l.starts_with(name))
             .map(|(k, &v)| (if v { 1 } else { 0 }, k.trim_start_matches(name).parse::<usize>().unwrap()))
@@ -72,154 +64,160 @@ fn build_number(state: &HashMap<String, bool>, name: &str) -> u64 {
             .sum::<u64>()
 }
 
-fn compute_result(state: &mut HashMap<String, bool>, ops: &HashMap<String, Gate>) -> u64 {
+fn compute_result<'a>(state: &mut HashMap<&'a str, bool>, ops: &HashMap<&'a str, Gate<'a>>) -> u64 {
     ops.keys().filter(|&l| l.starts_with("z")).for_each(|l| {
-        compute(state, ops, l.clone());
+        compute(state, ops, l);
     });
 
     build_number(state, "z")
 }
 
-impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+impl solver::Solver for Problem<'_> {
+    const IMPLEMENTATION: [solver::Implementation; 2] = [solver::Implementation::Complete, solver::Implementation::Hardcoded];
+
+    fn part_one(self: &Self) -> impl solver::Answer {
         compute_result(&mut self.0.clone(), &self.1)
     }
 
     // TODO interactive version??
-    fn part_two(self: &Self) -> impl std::fmt::Display {
+    fn part_two(self: &Self) -> impl solver::Answer {
         let expected = build_number(&self.0, "x") + build_number(&self.0, "y");
-        fn swap(state: &mut HashMap<String, Gate>, k1: String, k2: String) {
-            let old = state.insert(k2, state.get(&k1).unwrap().clone());
+        fn swap<'a>(state: &mut HashMap<&'a str, Gate<'a>>, k1: &'a str, k2: &'a str) {
+            let old = state.insert(k2, *state.get(k1).unwrap());
             state.insert(k1, old.unwrap());
         }
 
-        let diff = !(dbg!(expected) ^ dbg!(compute_result(&mut self.0.clone(), &self.1)));
+        let result = compute_result(&mut self.0.clone(), &self.1);
+        let diff = !(expected ^ result);
         let test_diff = diff - ((1 << 39) + (1 << 38) + (1 << 37) + (1 << 36) + (1 << 35) + (1 << 34) + (1 << 15) + (1 << 14) + (1 << 13) + (1 << 12) + (1 << 8) + (1 << 7));
-        println!("{diff} {test_diff}");
+        trace::debug!("expected {expected}, got {result} before swapping: matching bits {diff:b}, unexplained {test_diff:b}");
 
+        // swaps found by hand, only checked against the input
+        let swaps = [("rts", "z07"), ("z12", "jpj"), ("kgj", "z26"), ("chv", "vvw")];
         let ops = &mut self.1.clone();
-        swap(ops, "rts".to_string(), "z07".to_string());
-        swap(ops, "z12".to_string(), "jpj".to_string());
-        swap(ops, "kgj".to_string(), "z26".to_string());
-        swap(ops, "chv".to_string(), "vvw".to_string()); //chv,jpj,kgj,rts,vvw,z07,z12,z26 // this is the answer !!!
-        let diff = !(expected ^ dbg!(compute_result(&mut self.0.clone(), ops)));
-        let false_val = diff.count_ones();
-        println!("{diff} {false_val}");
-
-        0
+        swaps.iter().for_each(|&(k1, k2)| swap(ops, k1, k2));
+        let result = compute_result(&mut self.0.clone(), ops);
+        let diff = !(expected ^ result);
+        trace::debug!("got {result} after swapping: {} matching bits", diff.count_ones());
+
+        solver::Fallible(if result == expected {
+            Ok(swaps.iter().flat_map(|&(k1, k2)| [k1, k2]).sorted().join(","))
+        } else {
+            Err(solver::Error::NoSolution)
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("2024", format!("{}", Problem(HashMap::from([
-            ("x00".to_owned(), true),
-            ("x01".to_owned(), false),
-            ("x02".to_owned(), true),
-            ("x03".to_owned(), true),
-            ("x04".to_owned(), false),
-            ("y00".to_owned(), true),
-            ("y01".to_owned(), true),
-            ("y02".to_owned(), true),
-            ("y03".to_owned(), true),
-            ("y04".to_owned(), true),
+        assert_eq!(Ok("2024".to_string()), Problem(HashMap::from([
+            ("x00", true),
+            ("x01", false),
+            ("x02", true),
+            ("x03", true),
+            ("x04", false),
+            ("y00", true),
+            ("y01", true),
+            ("y02", true),
+            ("y03", true),
+            ("y04", true),
         ]), HashMap::from([
-            ("mjb".to_owned(), Gate::Xor("ntg".to_owned(), "fgs".to_owned())),
-            ("tnw".to_owned(), Gate::Or("y02".to_owned(), "x01".to_owned())),
-            ("z05".to_owned(), Gate::Or("kwq".to_owned(), "kpj".to_owned())),
-            ("fst".to_owned(), Gate::Or("x00".to_owned(), "x03".to_owned())),
-            ("z01".to_owned(), Gate::Xor("tgd".to_owned(), "rvg".to_owned())),
-            ("bfw".to_owned(), Gate::Or("vdt".to_owned(), "tnw".to_owned())),
-            ("z10".to_owned(), Gate::And("bfw".to_owned(), "frj".to_owned())),
-            ("bqk".to_owned(), Gate::Or("ffh".to_owned(), "nrd".to_owned())),
-            ("djm".to_owned(), Gate::And("y00".to_owned(), "y03".to_owned())),
-            ("psh".to_owned(), Gate::Or("y03".to_owned(), "y00".to_owned())),
-            ("z08".to_owned(), Gate::Or("bqk".to_owned(), "frj".to_owned())),
-            ("frj".to_owned(), Gate::Or("tnw".to_owned(), "fst".to_owned())),
-            ("z11".to_owned(), Gate::And("gnj".to_owned(), "tgd".to_owned())),
-            ("z00".to_owned(), Gate::Xor("bfw".to_owned(), "mjb".to_owned())),
-            ("vdt".to_owned(), Gate::Or("x03".to_owned(), "x00".to_owned())),
-            ("z02".to_owned(), Gate::And("gnj".to_owned(), "wpb".to_owned())),
-            ("kjc".to_owned(), Gate::And("x04".to_owned(), "y00".to_owned())),
-            ("qhw".to_owned(), Gate::Or("djm".to_owned(), "pbm".to_owned())),
-            ("hwm".to_owned(), Gate::And("nrd".to_owned(), "vdt".to_owned())),
-            ("rvg".to_owned(), Gate::And("kjc".to_owned(), "fst".to_owned())),
-            ("fgs".to_owned(), Gate::Or("y04".to_owned(), "y02".to_owned())),
-            ("pbm".to_owned(), Gate::And("y01".to_owned(), "x02".to_owned())),
-            ("kwq".to_owned(), Gate::Or("ntg".to_owned(), "kjc".to_owned())),
-            ("tgd".to_owned(), Gate::Xor("psh".to_owned(), "fgs".to_owned())),
-            ("z09".to_owned(), Gate::Xor("qhw".to_owned(), "tgd".to_owned())),
-            ("kpj".to_owned(), Gate::Or("pbm".to_owned(), "djm".to_owned())),
-            ("ffh".to_owned(), Gate::Xor("x03".to_owned(), "y03".to_owned())),
-            ("ntg".to_owned(), Gate::Xor("x00".to_owned(), "y04".to_owned())),
-            ("z06".to_owned(), Gate::Or("bfw".to_owned(), "bqk".to_owned())),
-            ("wpb".to_owned(), Gate::Xor("nrd".to_owned(), "fgs".to_owned())),
-            ("z04".to_owned(), Gate::Xor("frj".to_owned(), "qhw".to_owned())),
-            ("z07".to_owned(), Gate::Or("bqk".to_owned(), "frj".to_owned())),
-            ("nrd".to_owned(), Gate::Or("y03".to_owned(), "x01".to_owned())),
-            ("z03".to_owned(), Gate::And("hwm".to_owned(), "bqk".to_owned())),
-            ("z12".to_owned(), Gate::Xor("tgd".to_owned(), "rvg".to_owned())),
-            ("gnj".to_owned(), Gate::Or("tnw".to_owned(), "pbm".to_owned())),
-        ])).part_one()));
+            ("mjb", Gate::Xor("ntg", "fgs")),
+            ("tnw", Gate::Or("y02", "x01")),
+            ("z05", Gate::Or("kwq", "kpj")),
+            ("fst", Gate::Or("x00", "x03")),
+            ("z01", Gate::Xor("tgd", "rvg")),
+            ("bfw", Gate::Or("vdt", "tnw")),
+            ("z10", Gate::And("bfw", "frj")),
+            ("bqk", Gate::Or("ffh", "nrd")),
+            ("djm", Gate::And("y00", "y03")),
+            ("psh", Gate::Or("y03", "y00")),
+            ("z08", Gate::Or("bqk", "frj")),
+            ("frj", Gate::Or("tnw", "fst")),
+            ("z11", Gate::And("gnj", "tgd")),
+            ("z00", Gate::Xor("bfw", "mjb")),
+            ("vdt", Gate::Or("x03", "x00")),
+            ("z02", Gate::And("gnj", "wpb")),
+            ("kjc", Gate::And("x04", "y00")),
+            ("qhw", Gate::Or("djm", "pbm")),
+            ("hwm", Gate::And("nrd", "vdt")),
+            ("rvg", Gate::And("kjc", "fst")),
+            ("fgs", Gate::Or("y04", "y02")),
+            ("pbm", Gate::And("y01", "x02")),
+            ("kwq", Gate::Or("ntg", "kjc")),
+            ("tgd", Gate::Xor("psh", "fgs")),
+            ("z09", Gate::Xor("qhw", "tgd")),
+            ("kpj", Gate::Or("pbm", "djm")),
+            ("ffh", Gate::Xor("x03", "y03")),
+            ("ntg", Gate::Xor("x00", "y04")),
+            ("z06", Gate::Or("bfw", "bqk")),
+            ("wpb", Gate::Xor("nrd", "fgs")),
+            ("z04", Gate::Xor("frj", "qhw")),
+            ("z07", Gate::Or("bqk", "frj")),
+            ("nrd", Gate::Or("y03", "x01")),
+            ("z03", Gate::And("hwm", "bqk")),
+            ("z12", Gate::Xor("tgd", "rvg")),
+            ("gnj", Gate::Or("tnw", "pbm")),
+        ])).part_one().answer());
     }
 
     #[ignore = "currently hard-coded (found iteractively), ignore until better version"]
     #[test]
     fn part_two() {
-        assert_eq!("co,de,ka,ta", format!("{}", Problem(HashMap::from([
-            ("x00".to_owned(), true),
-            ("x01".to_owned(), false),
-            ("x02".to_owned(), true),
-            ("x03".to_owned(), true),
-            ("x04".to_owned(), false),
-            ("y00".to_owned(), true),
-            ("y01".to_owned(), true),
-            ("y02".to_owned(), true),
-            ("y03".to_owned(), true),
-            ("y04".to_owned(), true),
+        assert_eq!(Ok("co,de,ka,ta".to_string()), Problem(HashMap::from([
+            ("x00", true),
+            ("x01", false),
+            ("x02", true),
+            ("x03", true),
+            ("x04", false),
+            ("y00", true),
+            ("y01", true),
+            ("y02", true),
+            ("y03", true),
+            ("y04", true),
         ]), HashMap::from([
-            ("mjb".to_owned(), Gate::Xor("ntg".to_owned(), "fgs".to_owned())),
-            ("tnw".to_owned(), Gate::Or("y02".to_owned(), "x01".to_owned())),
-            ("z05".to_owned(), Gate::Or("kwq".to_owned(), "kpj".to_owned())),
-            ("fst".to_owned(), Gate::Or("x00".to_owned(), "x03".to_owned())),
-            ("z01".to_owned(), Gate::Xor("tgd".to_owned(), "rvg".to_owned())),
-            ("bfw".to_owned(), Gate::Or("vdt".to_owned(), "tnw".to_owned())),
-            ("z10".to_owned(), Gate::And("bfw".to_owned(), "frj".to_owned())),
-            ("bqk".to_owned(), Gate::Or("ffh".to_owned(), "nrd".to_owned())),
-            ("djm".to_owned(), Gate::And("y00".to_owned(), "y03".to_owned())),
-            ("psh".to_owned(), Gate::Or("y03".to_owned(), "y00".to_owned())),
-            ("z08".to_owned(), Gate::Or("bqk".to_owned(), "frj".to_owned())),
-            ("frj".to_owned(), Gate::Or("tnw".to_owned(), "fst".to_owned())),
-            ("z11".to_owned(), Gate::And("gnj".to_owned(), "tgd".to_owned())),
-            ("z00".to_owned(), Gate::Xor("bfw".to_owned(), "mjb".to_owned())),
-            ("vdt".to_owned(), Gate::Or("x03".to_owned(), "x00".to_owned())),
-            ("z02".to_owned(), Gate::And("gnj".to_owned(), "wpb".to_owned())),
-            ("kjc".to_owned(), Gate::And("x04".to_owned(), "y00".to_owned())),
-            ("qhw".to_owned(), Gate::Or("djm".to_owned(), "pbm".to_owned())),
-            ("hwm".to_owned(), Gate::And("nrd".to_owned(), "vdt".to_owned())),
-            ("rvg".to_owned(), Gate::And("kjc".to_owned(), "fst".to_owned())),
-            ("fgs".to_owned(), Gate::Or("y04".to_owned(), "y02".to_owned())),
-            ("pbm".to_owned(), Gate::And("y01".to_owned(), "x02".to_owned())),
-            ("kwq".to_owned(), Gate::Or("ntg".to_owned(), "kjc".to_owned())),
-            ("tgd".to_owned(), Gate::Xor("psh".to_owned(), "fgs".to_owned())),
-            ("z09".to_owned(), Gate::Xor("qhw".to_owned(), "tgd".to_owned())),
-            ("kpj".to_owned(), Gate::Or("pbm".to_owned(), "djm".to_owned())),
-            ("ffh".to_owned(), Gate::Xor("x03".to_owned(), "y03".to_owned())),
-            ("ntg".to_owned(), Gate::Xor("x00".to_owned(), "y04".to_owned())),
-            ("z06".to_owned(), Gate::Or("bfw".to_owned(), "bqk".to_owned())),
-            ("wpb".to_owned(), Gate::Xor("nrd".to_owned(), "fgs".to_owned())),
-            ("z04".to_owned(), Gate::Xor("frj".to_owned(), "qhw".to_owned())),
-            ("z07".to_owned(), Gate::Or("bqk".to_owned(), "frj".to_owned())),
-            ("nrd".to_owned(), Gate::Or("y03".to_owned(), "x01".to_owned())),
-            ("z03".to_owned(), Gate::And("hwm".to_owned(), "bqk".to_owned())),
-            ("z12".to_owned(), Gate::Xor("tgd".to_owned(), "rvg".to_owned())),
-            ("gnj".to_owned(), Gate::Or("tnw".to_owned(), "pbm".to_owned())),
-        ])).part_two()));
+            ("mjb", Gate::Xor("ntg", "fgs")),
+            ("tnw", Gate::Or("y02", "x01")),
+            ("z05", Gate::Or("kwq", "kpj")),
+            ("fst", Gate::Or("x00", "x03")),
+            ("z01", Gate::Xor("tgd", "rvg")),
+            ("bfw", Gate::Or("vdt", "tnw")),
+            ("z10", Gate::And("bfw", "frj")),
+            ("bqk", Gate::Or("ffh", "nrd")),
+            ("djm", Gate::And("y00", "y03")),
+            ("psh", Gate::Or("y03", "y00")),
+            ("z08", Gate::Or("bqk", "frj")),
+            ("frj", Gate::Or("tnw", "fst")),
+            ("z11", Gate::And("gnj", "tgd")),
+            ("z00", Gate::Xor("bfw", "mjb")),
+            ("vdt", Gate::Or("x03", "x00")),
+            ("z02", Gate::And("gnj", "wpb")),
+            ("kjc", Gate::And("x04", "y00")),
+            ("qhw", Gate::Or("djm", "pbm")),
+            ("hwm", Gate::And("nrd", "vdt")),
+            ("rvg", Gate::And("kjc", "fst")),
+            ("fgs", Gate::Or("y04", "y02")),
+            ("pbm", Gate::And("y01", "x02")),
+            ("kwq", Gate::Or("ntg", "kjc")),
+            ("tgd", Gate::Xor("psh", "fgs")),
+            ("z09", Gate::Xor("qhw", "tgd")),
+            ("kpj", Gate::Or("pbm", "djm")),
+            ("ffh", Gate::Xor("x03", "y03")),
+            ("ntg", Gate::Xor("x00", "y04")),
+            ("z06", Gate::Or("bfw", "bqk")),
+            ("wpb", Gate::Xor("nrd", "fgs")),
+            ("z04", Gate::Xor("frj", "qhw")),
+            ("z07", Gate::Or("bqk", "frj")),
+            ("nrd", Gate::Or("y03", "x01")),
+            ("z03", Gate::And("hwm", "bqk")),
+            ("z12", Gate::Xor("tgd", "rvg")),
+            ("gnj", Gate::Or("tnw", "pbm")),
+        ])).part_two().answer());
     }
 }
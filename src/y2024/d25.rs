@@ -1,5 +1,5 @@
 use std::{array, io::{BufRead, Lines}};
-use crate::solver;
+use crate::{map::Grid, parse, solver};
 use anyhow::Result;
 use itertools::Itertools;
 
@@ -15,25 +15,32 @@ impl<B: BufRead> TryFrom<Lines<B>> for Problem {
     type Error = anyhow::Error;
 
     fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
-        let a = value.collect::<Result<Vec<_>, _>>()?;
-        Ok(Self(a.split(|s| s.is_empty()).map(|s| {
-            if s[0].starts_with('#') {
-                let s = &s[1..];
-                Schematic::Lock(array::from_fn(|i| {
-                    s.iter().find_position(|s| s.as_bytes()[i] == b'.').unwrap().0
-                }))
-            } else {
-                let s = &s[1..];
-                Schematic::Key(array::from_fn(|i| {
-                    5 - s.iter().find_position(|s| s.as_bytes()[i] == b'#').unwrap().0
-                }))
+        let text = parse::Text::read(value)?;
+        Ok(Self(text.sections().into_iter().map(|s| {
+            let first = s[0];
+            let (grid, []) = Grid::parse(s, [], |c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            })?;
+            if grid.size() != (5, 7) {
+                return Err(first.error("expected a schematic of 5 columns and 7 rows"));
             }
-        }).collect()))
+            let lock = grid.row(0).iter().all(|&b| b);
+            if !lock && !grid.row(6).iter().all(|&b| b) {
+                return Err(first.error("expected a filled top or bottom row"));
+            }
+            // Besides its pin, each column has a filled cell in the base of the lock or of the key.
+            let heights = array::from_fn(|x| grid.column(x).filter(|&&b| b).count() - 1);
+            Ok(if lock { Schematic::Lock(heights) } else { Schematic::Key(heights) })
+        }).collect::<Result<_, parse::Error>>()?))
     }
 }
 
 impl solver::Solver for Problem {
-    fn part_one(self: &Self) -> impl std::fmt::Display {
+    const IMPLEMENTATION: [solver::Implementation; 2] = [solver::Implementation::Complete, solver::Implementation::Missing];
+
+    fn part_one(self: &Self) -> impl solver::Answer {
         let (locks, keys): (Vec<&[usize; 5]>, Vec<&[usize; 5]>) = self.0.iter().partition_map(|s| match s {
             Schematic::Lock(v) => itertools::Either::Left(v),
             Schematic::Key(v) => itertools::Either::Right(v),
@@ -41,25 +48,44 @@ impl solver::Solver for Problem {
         locks.into_iter().cartesian_product(keys.into_iter()).filter(|&(lock, key)| lock[..].iter().zip(key[..].iter()).all(|(lvl1, lvl2)| lvl1 + lvl2 <= 5)).count()
     }
 
-    fn part_two(self: &Self) -> impl std::fmt::Display {
-        0
+    fn part_two(self: &Self) -> impl solver::Answer {
+        solver::Fallible::<u64>(Err(solver::Error::NotImplemented))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solver::Solver;
+    use solver::{Answer, Solver};
 
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!("3", format!("{}", Problem(vec![
+        assert_eq!(Ok("3".to_string()), Problem(vec![
             Schematic::Lock([0,5,3,4,3]),
             Schematic::Lock([1,2,0,5,3]),
             Schematic::Key([5,0,2,1,3]),
             Schematic::Key([4,3,4,0,2]),
             Schematic::Key([3,0,2,0,1]),
-        ]).part_one()));
+        ]).part_one().answer());
+    }
+
+    #[test]
+    fn parse() -> Result<()> {
+        let pb: Problem = "#####\n.####\n.####\n.####\n.#.#.\n.#...\n.....
+
+#####\n##.##\n.#.##\n...##\n...#.\n...#.\n.....
+
+.....\n#....\n#....\n#...#\n#.#.#\n#.###\n#####
+
+.....\n.....\n#.#..\n###..\n###.#\n###.#\n#####
+
+.....\n.....\n.....\n#....\n#.#..\n#.#.#\n#####".as_bytes().lines().try_into()?;
+        assert!(matches!(pb.0[1], Schematic::Lock([1, 2, 0, 5, 3])));
+        assert!(matches!(pb.0[2], Schematic::Key([5, 0, 2, 1, 3])));
+        assert_eq!("3", pb.part_one().answer()?);
+        assert!(Problem::try_from("#####\n.####\n.....".as_bytes().lines()).is_err());
+        assert!(Problem::try_from(".....\n.####\n.....\n.....\n.....\n.....\n.....".as_bytes().lines()).is_err());
+        Ok(())
     }
 }
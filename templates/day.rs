@@ -0,0 +1,50 @@
+use std::io::{BufRead, Lines};
+use crate::solver;
+use anyhow::Result;
+
+pub struct Problem(Vec<String>);
+
+impl<B: BufRead> TryFrom<Lines<B>> for Problem {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Lines<B>) -> Result<Self, Self::Error> {
+        Ok(Self(value.collect::<Result<Vec<_>, _>>()?))
+    }
+}
+
+impl solver::Solver for Problem {
+    const IMPLEMENTATION: [solver::Implementation; 2] = [solver::Implementation::Missing; 2];
+
+    fn part_one(&self) -> impl solver::Answer {
+        let _lines = &self.0;
+        solver::Fallible::<u64>(Err(solver::Error::NotImplemented))
+    }
+
+    fn part_two(&self) -> impl solver::Answer {
+        solver::Fallible::<u64>(Err(solver::Error::NotImplemented))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solver::{Answer, Solver};
+    use test_case::test_case;
+
+    use super::*;
+
+    #[ignore = "not implemented yet"]
+    #[test_case("", "0")]
+    fn part_one(example: &str, result: &str) -> anyhow::Result<()> {
+        let pb: Problem = example.as_bytes().lines().try_into()?;
+        assert_eq!(result, pb.part_one().answer()?);
+        Ok(())
+    }
+
+    #[ignore = "not implemented yet"]
+    #[test_case("", "0")]
+    fn part_two(example: &str, result: &str) -> anyhow::Result<()> {
+        let pb: Problem = example.as_bytes().lines().try_into()?;
+        assert_eq!(result, pb.part_two().answer()?);
+        Ok(())
+    }
+}